@@ -0,0 +1,74 @@
+use serde_json::{json, Value};
+use std::time::Duration;
+use three_style_lib::{
+    commutator::types::Commutator,
+    moves::{Alg, Metric},
+};
+
+/// Search parameters reported along with the results.
+pub struct Metadata<'a> {
+    pub cycle: String,
+    pub gen: &'a str,
+    pub depth: u8,
+    pub duration: Duration,
+    pub timed_out: bool,
+}
+
+impl Metadata<'_> {
+    fn to_json(&self, count: usize) -> Value {
+        json!({
+            "cycle": self.cycle,
+            "gen": self.gen,
+            "depth": self.depth,
+            "elapsed": self.duration.as_secs_f64(),
+            "timed_out": self.timed_out,
+            "count": count,
+        })
+    }
+}
+
+fn metrics_json(alg: &Alg) -> Value {
+    json!({
+        "htm": alg.length(Metric::Htm),
+        "qtm": alg.length(Metric::Qtm),
+        "stm": alg.length(Metric::Stm),
+        "etm": alg.length(Metric::Etm),
+    })
+}
+
+fn commutator_json(commutator: &Commutator) -> Value {
+    let expanded = commutator.expand();
+    let reduced = expanded.clone().reduce();
+
+    json!({
+        "notation": commutator.to_string(),
+        "setup": commutator.setup.as_ref().map(|s| s.to_string()),
+        "interchange": commutator.interchange.to_string(),
+        "insertion": commutator.insertion.to_string(),
+        "insertion_first": commutator.insertion_first,
+        "expanded": expanded.to_string(),
+        "reduced": reduced.to_string(),
+        "expanded_length": expanded.len(),
+        "reduced_length": reduced.len(),
+        "metrics": metrics_json(&reduced),
+    })
+}
+
+/// Prints a single JSON document containing the metadata and every result.
+pub fn print_json(commutators: &[Commutator], metadata: &Metadata) {
+    let mut value = metadata.to_json(commutators.len());
+
+    value["results"] = commutators.iter().map(commutator_json).collect();
+
+    println!("{value:#}");
+}
+
+/// Prints a single result line, results are followed by a summary line.
+pub fn print_ndjson_result(commutator: &Commutator) {
+    println!("{}", commutator_json(commutator));
+}
+
+/// Prints the summary line containing the metadata.
+pub fn print_ndjson_summary(count: usize, metadata: &Metadata) {
+    println!("{}", json!({ "summary": metadata.to_json(count) }));
+}
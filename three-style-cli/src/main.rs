@@ -1,20 +1,34 @@
+mod json;
+
 use clap::{
     builder::styling::{AnsiColor, Color, Style},
-    Parser, Subcommand,
+    Parser, Subcommand, ValueEnum,
 };
+use json::{print_json, print_ndjson_result, print_ndjson_summary, Metadata};
 use std::{
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    path::Path,
     process,
     str::FromStr,
     time::{Duration, Instant},
 };
 use three_style_lib::{
     commutator::{
-        finder::{find_corner_commutators, find_edge_commutators},
+        finder::CommutatorSearch,
+        ranking::{rank, PureFirst, Ranker, ReducedLength, SetupLength},
+        table::CaseTable,
         types::{Commutator, Cycle},
     },
+    database::{Database, DatabaseBuilder},
     error::Error,
-    moves::MoveKind,
-    sticker::{Corner, Edge},
+    moves::{ErgonomicScorer, Metric, MoveKind},
+    sheet::{
+        generator::{generate_corner_sheet, generate_edge_sheet, generate_sheet_from_table},
+        grid::{format_grid, GridFormat},
+        types::Sheet,
+    },
+    sticker::{Corner, Edge, LetterScheme, Sticker},
 };
 
 #[derive(Parser)]
@@ -27,27 +41,182 @@ struct Cli {
 
 impl Cli {
     fn exec(self) -> Result<(), Error> {
-        if let Some(Command::Search {
-            corners,
-            edges,
-            gen,
-            depth,
-            raw,
-        }) = self.command
-        {
-            let allowed_moves = gen
-                .chars()
-                .map(|c| MoveKind::from_str(&c.to_string()))
-                .collect::<Result<Vec<_>, _>>()?;
-            let start = Instant::now();
-            let commutators = match (corners, edges) {
-                (Some(corners), None) => search_corner_commutators(corners, allowed_moves, depth)?,
-                (None, Some(edges)) => search_edge_commutators(edges, allowed_moves, depth)?,
-                _ => unreachable!(),
-            };
-            let end = Instant::now();
-
-            print_commutators(commutators, end - start, raw);
+        match self.command {
+            Some(Command::Search {
+                corners,
+                edges,
+                gen,
+                depth,
+                raw,
+                scheme,
+                format,
+                threads,
+                limit,
+                timeout,
+                dedup,
+                sort,
+                metric,
+                weights,
+                transposition_table,
+                db,
+            }) => {
+                let allowed_moves = parse_moves(&gen)?;
+                let scheme = parse_scheme(scheme)?;
+                let database = db.map(Database::open_mmap).transpose()?;
+                let scorer = match weights {
+                    Some(path) => ErgonomicScorer::from_file(path)?,
+                    None => ErgonomicScorer::default(),
+                };
+                let options = SearchOptions {
+                    gen: &gen,
+                    depth,
+                    threads,
+                    raw,
+                    format,
+                    limit,
+                    timeout,
+                    dedup,
+                    transposition_table,
+                    sort: &sort,
+                    metric: metric.into(),
+                    scorer,
+                    database: database.as_ref(),
+                };
+
+                match (corners, edges) {
+                    (Some(corners), None) => {
+                        let cycle = parse_cycle(&corners, |l| scheme.corner(l))?;
+                        run_search::<Corner>(cycle, &allowed_moves, &options)?;
+                    }
+                    (None, Some(edges)) => {
+                        let cycle = parse_cycle(&edges, |l| scheme.edge(l))?;
+                        run_search::<Edge>(cycle, &allowed_moves, &options)?;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Some(Command::Sheet {
+                corners,
+                edges,
+                gen,
+                depth,
+                scheme,
+                format,
+                letters,
+                threads,
+                metric,
+                table,
+            }) => {
+                let allowed_moves = parse_moves(&gen)?;
+                let scheme = parse_scheme(scheme)?;
+                let start = Instant::now();
+
+                match (corners, edges) {
+                    (Some(buffer), None) => {
+                        let buffer = parse_target(&buffer, |l| scheme.corner(l))?;
+                        let sheet = match table {
+                            true => generate_sheet_from_table(
+                                buffer,
+                                &CaseTable::build(&allowed_moves, depth),
+                            ),
+                            false => generate_corner_sheet(buffer, &allowed_moves, depth, threads),
+                        };
+                        let label = |c: Corner| match letters {
+                            true => scheme.corner_letter(c).to_string(),
+                            false => c.to_string(),
+                        };
+                        print_sheet(&sheet, start.elapsed(), format, metric.into(), label);
+                    }
+                    (None, Some(buffer)) => {
+                        let buffer = parse_target(&buffer, |l| scheme.edge(l))?;
+                        let sheet = match table {
+                            true => generate_sheet_from_table(
+                                buffer,
+                                &CaseTable::build(&allowed_moves, depth),
+                            ),
+                            false => generate_edge_sheet(buffer, &allowed_moves, depth, threads),
+                        };
+                        let label = |e: Edge| match letters {
+                            true => scheme.edge_letter(e).to_string(),
+                            false => e.to_string(),
+                        };
+                        print_sheet(&sheet, start.elapsed(), format, metric.into(), label);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Some(Command::Db {
+                command:
+                    DbCommand::Build {
+                        path,
+                        gen,
+                        depth,
+                        corners,
+                        edges,
+                        best,
+                        threads,
+                    },
+            }) => {
+                let movesets = gen
+                    .iter()
+                    .map(|g| parse_moves(g))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let start = Instant::now();
+                let mut builder = DatabaseBuilder::new(best).threads(threads);
+
+                for allowed_moves in &movesets {
+                    if corners {
+                        builder = builder.add::<Corner>(allowed_moves, depth);
+                    }
+                    if edges {
+                        builder = builder.add::<Edge>(allowed_moves, depth);
+                    }
+                }
+
+                builder.write(&path)?;
+
+                let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+                let duration = start.elapsed().as_secs_f32();
+
+                println!("Saved {green}{path}{green:#} in {duration:.2}s.");
+            }
+            Some(Command::Db {
+                command:
+                    DbCommand::Query {
+                        path,
+                        corners,
+                        edges,
+                        gen,
+                        depth,
+                        raw,
+                        scheme,
+                        metric,
+                    },
+            }) => {
+                let allowed_moves = parse_moves(&gen)?;
+                let scheme = parse_scheme(scheme)?;
+                let start = Instant::now();
+                let database = Database::open_mmap(path)?;
+                let commutators = match (corners, edges) {
+                    (Some(corners), None) => {
+                        let cycle = parse_cycle::<Corner, _>(&corners, |l| scheme.corner(l))?;
+                        database.get(cycle, &allowed_moves, depth)?
+                    }
+                    (None, Some(edges)) => {
+                        let cycle = parse_cycle::<Edge, _>(&edges, |l| scheme.edge(l))?;
+                        database.get(cycle, &allowed_moves, depth)?
+                    }
+                    _ => unreachable!(),
+                }
+                .unwrap_or_default();
+
+                for commutator in &commutators {
+                    print_commutator(commutator, raw, metric.into());
+                }
+
+                print_summary(commutators.len(), start.elapsed(), false);
+            }
+            None => {}
         }
 
         Ok(())
@@ -63,10 +232,10 @@ enum Command {
         .args(&["corners", "edges"]),
     ))]
     Search {
-        #[arg(long, short, num_args(3), help = "Corner cycle")]
+        #[arg(long, short, num_args(3), help = "Corner cycle (stickers or letters)")]
         corners: Option<Vec<String>>,
 
-        #[arg(long, short, num_args(3), help = "Edge cycle")]
+        #[arg(long, short, num_args(3), help = "Edge cycle (stickers or letters)")]
         edges: Option<Vec<String>>,
 
         #[arg(long, short, help = "Allwed movesets")]
@@ -77,53 +246,449 @@ enum Command {
 
         #[arg(long, short, help = "Display the non-reduced algorithm")]
         raw: bool,
+
+        #[arg(
+            long,
+            short,
+            help = "Letter scheme file or 48 letters string (corners then edges in Speffz order)"
+        )]
+        scheme: Option<String>,
+
+        #[arg(long, short, value_enum, default_value_t = OutputFormat::Text, help = "Output format")]
+        format: OutputFormat,
+
+        #[arg(long, short, default_value_t = 1, help = "Number of search threads")]
+        threads: usize,
+
+        #[arg(
+            long,
+            short,
+            help = "Stop the search after the given number of results, or keep the best ones with --sort"
+        )]
+        limit: Option<NonZeroUsize>,
+
+        #[arg(
+            long,
+            value_parser = parse_timeout,
+            help = "Stop the search after the given number of seconds"
+        )]
+        timeout: Option<Duration>,
+
+        #[arg(
+            long,
+            help = "Skip commutators with the same reduced algorithm as a previous one"
+        )]
+        dedup: bool,
+
+        #[arg(
+            long,
+            help = "Expand setup states reached by different move sequences only once"
+        )]
+        transposition_table: bool,
+
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            help = "Sort the results once the search is done, criteria are comma separated"
+        )]
+        sort: Vec<SortKey>,
+
+        #[arg(
+            long,
+            short,
+            value_enum,
+            default_value_t = MetricKind::Stm,
+            help = "Move count metric used for displaying and sorting"
+        )]
+        metric: MetricKind,
+
+        #[arg(long, short, help = "Weights file used for the ergonomic sort")]
+        weights: Option<String>,
+
+        #[arg(
+            long,
+            help = "Commutator database consulted before searching, see the db command"
+        )]
+        db: Option<String>,
+    },
+
+    #[command(about = "Generate the commutators of every target pair for the given buffer")]
+    #[clap(group(
+    clap::ArgGroup::new("piece")
+        .required(true)
+        .args(&["corners", "edges"]),
+    ))]
+    Sheet {
+        #[arg(long, short, help = "Corner buffer (sticker or letter)")]
+        corners: Option<String>,
+
+        #[arg(long, short, help = "Edge buffer (sticker or letter)")]
+        edges: Option<String>,
+
+        #[arg(long, short, help = "Allwed movesets")]
+        gen: String,
+
+        #[arg(long, short, help = "Maximum search depth")]
+        depth: u8,
+
+        #[arg(
+            long,
+            short,
+            help = "Letter scheme file or 48 letters string (corners then edges in Speffz order)"
+        )]
+        scheme: Option<String>,
+
+        #[arg(long, short, value_enum, default_value_t = SheetFormat::Text, help = "Output format")]
+        format: SheetFormat,
+
+        #[arg(long, short, help = "Label targets with letters instead of stickers")]
+        letters: bool,
+
+        #[arg(long, short, default_value_t = 1, help = "Number of search threads")]
+        threads: usize,
+
+        #[arg(
+            long,
+            short,
+            value_enum,
+            default_value_t = MetricKind::Stm,
+            help = "Move count metric used for displaying"
+        )]
+        metric: MetricKind,
+
+        #[arg(
+            long,
+            help = "Answer every case from a precomputed table of pure commutators and setups"
+        )]
+        table: bool,
+    },
+
+    #[command(about = "Build or query a database of the best commutators of every case")]
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
     },
 }
 
-fn search_corner_commutators(
-    corners: Vec<String>,
-    allowed_moves: Vec<MoveKind>,
-    depth: u8,
-) -> Result<Vec<Commutator>, Error> {
-    let corners = corners
-        .into_iter()
-        .map(|c| Corner::from_str(&c))
-        .collect::<Result<Vec<_>, _>>()?;
-    let cycle = Cycle::new(corners[0], corners[1], corners[2]);
-    let results = find_corner_commutators(cycle, &allowed_moves, depth);
+#[derive(Subcommand)]
+enum DbCommand {
+    #[command(about = "Search the best commutators of every case and save them to a file")]
+    #[clap(group(
+    clap::ArgGroup::new("pieces")
+        .required(true)
+        .multiple(true)
+        .args(&["corners", "edges"]),
+    ))]
+    Build {
+        #[arg(help = "Database file")]
+        path: String,
+
+        #[arg(
+            long,
+            short,
+            required = true,
+            help = "Allowed movesets, can be repeated"
+        )]
+        gen: Vec<String>,
+
+        #[arg(long, short, help = "Maximum search depth")]
+        depth: u8,
+
+        #[arg(long, short, help = "Store the corner cases")]
+        corners: bool,
+
+        #[arg(long, short, help = "Store the edge cases")]
+        edges: bool,
+
+        #[arg(
+            long,
+            short = 'n',
+            default_value_t = 5,
+            help = "Number of commutators stored for each case"
+        )]
+        best: usize,
+
+        #[arg(long, short, default_value_t = 1, help = "Number of search threads")]
+        threads: usize,
+    },
+
+    #[command(about = "Print the commutators stored for the given three cycle")]
+    #[clap(group(
+    clap::ArgGroup::new("piece")
+        .required(true)
+        .args(&["corners", "edges"]),
+    ))]
+    Query {
+        #[arg(help = "Database file")]
+        path: String,
+
+        #[arg(long, short, num_args(3), help = "Corner cycle (stickers or letters)")]
+        corners: Option<Vec<String>>,
+
+        #[arg(long, short, num_args(3), help = "Edge cycle (stickers or letters)")]
+        edges: Option<Vec<String>>,
+
+        #[arg(long, short, help = "Allwed movesets")]
+        gen: String,
+
+        #[arg(long, short, help = "Maximum commutator depth")]
+        depth: u8,
+
+        #[arg(long, short, help = "Display the non-reduced algorithm")]
+        raw: bool,
 
-    Ok(results)
+        #[arg(
+            long,
+            short,
+            help = "Letter scheme file or 48 letters string (corners then edges in Speffz order)"
+        )]
+        scheme: Option<String>,
+
+        #[arg(
+            long,
+            short,
+            value_enum,
+            default_value_t = MetricKind::Stm,
+            help = "Move count metric used for displaying"
+        )]
+        metric: MetricKind,
+    },
 }
 
-fn search_edge_commutators(
-    edges: Vec<String>,
-    allowed_moves: Vec<MoveKind>,
-    depth: u8,
-) -> Result<Vec<Commutator>, Error> {
-    let edges = edges
-        .into_iter()
-        .map(|c| Edge::from_str(&c))
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Ndjson,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortKey {
+    /// Reduced move count in the chosen metric
+    Length,
+    /// Setup move count
+    Setup,
+    /// Pure commutators first
+    Pure,
+    /// Ergonomic score, see --weights
+    Ergonomic,
+}
+
+impl SortKey {
+    fn ranker(self, metric: Metric, scorer: &ErgonomicScorer) -> Box<dyn Ranker> {
+        match self {
+            SortKey::Length => Box::new(ReducedLength(metric)),
+            SortKey::Setup => Box::new(SetupLength),
+            SortKey::Pure => Box::new(PureFirst),
+            SortKey::Ergonomic => Box::new(scorer.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MetricKind {
+    /// Half turn metric
+    Htm,
+    /// Quarter turn metric
+    Qtm,
+    /// Slice turn metric
+    Stm,
+    /// Execution turn metric
+    Etm,
+}
+
+impl From<MetricKind> for Metric {
+    fn from(value: MetricKind) -> Self {
+        match value {
+            MetricKind::Htm => Metric::Htm,
+            MetricKind::Qtm => Metric::Qtm,
+            MetricKind::Stm => Metric::Stm,
+            MetricKind::Etm => Metric::Etm,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SheetFormat {
+    Text,
+    Csv,
+    Tsv,
+}
+
+fn parse_moves(gen: &str) -> Result<Vec<MoveKind>, Error> {
+    gen.chars()
+        .map(|c| MoveKind::from_str(&c.to_string()))
+        .collect()
+}
+
+/// Parses a finite and non-negative number of seconds.
+fn parse_timeout(seconds: &str) -> Result<Duration, String> {
+    let seconds = f64::from_str(seconds).map_err(|e| e.to_string())?;
+
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| "expected a finite and non-negative number of seconds".to_owned())
+}
+
+fn parse_scheme(scheme: Option<String>) -> Result<LetterScheme, Error> {
+    match scheme {
+        Some(scheme) if Path::new(&scheme).is_file() => LetterScheme::from_file(scheme),
+        Some(scheme) => LetterScheme::from_str(&scheme),
+        None => Ok(LetterScheme::default()),
+    }
+}
+
+/// Parses a target as a letter if it is a single character or as a sticker otherwise.
+fn parse_target<T, F>(target: &str, from_letter: F) -> Result<T, Error>
+where
+    T: FromStr<Err = Error>,
+    F: Fn(char) -> Option<T>,
+{
+    let mut chars = target.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => from_letter(letter).ok_or(Error::InvalidLetter(letter)),
+        _ => T::from_str(target),
+    }
+}
+
+fn parse_cycle<T, F>(targets: &[String], from_letter: F) -> Result<Cycle<T>, Error>
+where
+    T: Sticker + FromStr<Err = Error>,
+    F: Fn(char) -> Option<T>,
+{
+    let targets = targets
+        .iter()
+        .map(|t| parse_target(t, &from_letter))
         .collect::<Result<Vec<_>, _>>()?;
-    let cycle = Cycle::new(edges[0], edges[1], edges[2]);
-    let results = find_edge_commutators(cycle, &allowed_moves, depth);
 
-    Ok(results)
+    Ok(Cycle::new(targets[0], targets[1], targets[2]))
 }
 
-fn print_commutators(commutators: Vec<Commutator>, duration: Duration, raw: bool) {
-    let count = commutators.len();
-    let duration = duration.as_secs_f32();
-    let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+struct SearchOptions<'a> {
+    gen: &'a str,
+    depth: u8,
+    threads: usize,
+    raw: bool,
+    format: OutputFormat,
+    limit: Option<NonZeroUsize>,
+    timeout: Option<Duration>,
+    dedup: bool,
+    transposition_table: bool,
+    sort: &'a [SortKey],
+    metric: Metric,
+    scorer: ErgonomicScorer,
+    database: Option<&'a Database>,
+}
 
-    for comm in commutators {
-        let bold = Style::new().bold();
-        let cyan = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan)));
-        let alg = comm.expand();
-        let alg = if raw { alg } else { alg.reduce() };
+/// Prints the commutators as they are found, except for JSON or sorted results which are printed at the end.
+/// Commutators stored in the database are used instead of searching if there are any.
+fn run_search<T>(
+    cycle: Cycle<T>,
+    allowed_moves: &[MoveKind],
+    options: &SearchOptions,
+) -> Result<(), Error>
+where
+    T: Sticker,
+{
+    let stored = match options.database {
+        Some(database) => database.get(cycle, allowed_moves, options.depth)?,
+        None => None,
+    };
+    let mut search = CommutatorSearch::new(cycle, allowed_moves, options.depth)
+        .threads(options.threads)
+        .dedup(options.dedup)
+        .transposition_table(options.transposition_table);
 
-        println!("{bold}{comm}{bold:#}: {alg} {cyan}({}){cyan:#}", alg.len());
+    if let Some(timeout) = options.timeout {
+        search = search.timeout(timeout);
     }
 
+    let limit = options.limit.map_or(usize::MAX, NonZeroUsize::get);
+    let start = Instant::now();
+    let mut commutators = Vec::new();
+    let is_sorted = !options.sort.is_empty();
+
+    let mut push = |commutator: Commutator| {
+        if !is_sorted {
+            print_result(&commutator, options);
+        }
+
+        commutators.push(commutator);
+
+        // sorted results are only limited once ranked
+        match is_sorted || commutators.len() < limit {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        }
+    };
+
+    let flow = match stored {
+        Some(stored) => stored.into_iter().try_for_each(push),
+        None => search.for_each(&mut push),
+    };
+
+    let duration = start.elapsed();
+    let timed_out = flow.is_break() && (is_sorted || commutators.len() < limit);
+
+    if is_sorted {
+        let rankers = options
+            .sort
+            .iter()
+            .map(|k| k.ranker(options.metric, &options.scorer))
+            .collect::<Vec<_>>();
+
+        rank(&mut commutators, rankers.as_slice());
+        commutators.truncate(limit);
+
+        for commutator in &commutators {
+            print_result(commutator, options);
+        }
+    }
+
+    let metadata = Metadata {
+        cycle: cycle.to_string(),
+        gen: options.gen,
+        depth: options.depth,
+        duration,
+        timed_out,
+    };
+
+    match options.format {
+        OutputFormat::Text => print_summary(commutators.len(), duration, timed_out),
+        OutputFormat::Json => print_json(&commutators, &metadata),
+        OutputFormat::Ndjson => print_ndjson_summary(commutators.len(), &metadata),
+    }
+
+    Ok(())
+}
+
+/// Prints a single result, JSON results are all printed at once with the summary.
+fn print_result(commutator: &Commutator, options: &SearchOptions) {
+    match options.format {
+        OutputFormat::Text => print_commutator(commutator, options.raw, options.metric),
+        OutputFormat::Ndjson => print_ndjson_result(commutator),
+        OutputFormat::Json => {}
+    }
+}
+
+fn print_commutator(commutator: &Commutator, raw: bool, metric: Metric) {
+    let bold = Style::new().bold();
+    let cyan = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan)));
+    let alg = commutator.expand();
+    let alg = if raw { alg } else { alg.reduce() };
+
+    println!(
+        "{bold}{commutator}{bold:#}: {alg} {cyan}({}){cyan:#}",
+        alg.length(metric)
+    );
+}
+
+fn print_summary(count: usize, duration: Duration, timed_out: bool) {
+    let duration = duration.as_secs_f32();
+    let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+    let yellow = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+
     if count > 0 {
         println!(
             "\nFound {green}{count}{green:#} result{} in {duration:.2}s.",
@@ -132,6 +697,58 @@ fn print_commutators(commutators: Vec<Commutator>, duration: Duration, raw: bool
     } else {
         println!("No result found.");
     }
+
+    if timed_out {
+        println!("{yellow}Search timed out, results are partial.{yellow:#}");
+    }
+}
+
+fn print_sheet<T, F>(
+    sheet: &Sheet<T>,
+    duration: Duration,
+    format: SheetFormat,
+    metric: Metric,
+    label: F,
+) where
+    T: Sticker,
+    F: Fn(T) -> String,
+{
+    let grid_format = match format {
+        SheetFormat::Text => None,
+        SheetFormat::Csv => Some(GridFormat::Csv),
+        SheetFormat::Tsv => Some(GridFormat::Tsv),
+    };
+
+    if let Some(grid_format) = grid_format {
+        print!("{}", format_grid(sheet, grid_format, label));
+        return;
+    }
+
+    let bold = Style::new().bold();
+    let cyan = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan)));
+    let green = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+    let duration = duration.as_secs_f32();
+
+    for entry in &sheet.entries {
+        let cycle = entry.cycle;
+        let targets = format!("{} {}", label(cycle.second()), label(cycle.third()));
+
+        match &entry.commutator {
+            Some(comm) => {
+                let alg = comm.expand().reduce();
+                println!(
+                    "{bold}{targets}{bold:#}: {comm} {cyan}({}){cyan:#}",
+                    alg.length(metric)
+                );
+            }
+            None => println!("{bold}{targets}{bold:#}: -"),
+        }
+    }
+
+    let total = sheet.entries.len();
+    let found = total - sheet.missing().count();
+
+    println!("\nFound {green}{found}{green:#}/{total} cases in {duration:.2}s.");
 }
 
 fn print_error(error: Error) {
@@ -0,0 +1,125 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use three_style_lib::{
+    alg,
+    commutator::types::Cycle,
+    cubie::CubieCube,
+    facelet::{Facelet, FaceletCube},
+    moves::{Alg, Move, MoveKind},
+    sticker::Corner,
+};
+
+/// Operations done on the state at each node of the setup search.
+trait SearchState: Sized {
+    fn next(&self, m: Move) -> Self;
+    fn read(&self, position: Facelet) -> Facelet;
+}
+
+impl SearchState for FaceletCube {
+    fn next(&self, m: Move) -> Self {
+        self.apply_move(m)
+    }
+
+    fn read(&self, position: Facelet) -> Facelet {
+        self[position]
+    }
+}
+
+impl SearchState for CubieCube {
+    fn next(&self, m: Move) -> Self {
+        self.apply_move(m)
+    }
+
+    fn read(&self, position: Facelet) -> Facelet {
+        self.facelet(position)
+    }
+}
+
+fn scramble() -> Vec<Move> {
+    alg!("R U2 F' D L2 B' M E' S2 r' u f2 R' U D2 F")
+        .into_iter()
+        .collect()
+}
+
+fn apply_moves(c: &mut Criterion) {
+    let moves = scramble();
+    let mut group = c.benchmark_group("apply_moves");
+
+    group.bench_function("facelet", |b| {
+        b.iter(|| {
+            moves.iter().fold(FaceletCube::default(), |cube, m| {
+                cube.apply_move(black_box(*m))
+            })
+        })
+    });
+
+    group.bench_function("cubie", |b| {
+        b.iter(|| {
+            moves.iter().fold(CubieCube::default(), |cube, m| {
+                cube.apply_move(black_box(*m))
+            })
+        })
+    });
+
+    group.finish();
+}
+
+fn conversions(c: &mut Criterion) {
+    let alg = Alg::new(scramble());
+    let facelet = FaceletCube::default().apply_alg(&alg);
+    let cubie = CubieCube::default().apply_alg(&alg);
+    let mut group = c.benchmark_group("conversions");
+
+    group.bench_function("to_facelet", |b| {
+        b.iter(|| FaceletCube::from(black_box(&cubie)))
+    });
+
+    group.bench_function("to_cubie", |b| {
+        b.iter(|| CubieCube::try_from(black_box(&facelet)))
+    });
+
+    group.finish();
+}
+
+/// Visits every setup sequence up to the depth like the commutator finder does,
+/// reading the facelets of the tracked cycle at each node. Returns the number of nodes.
+fn walk<S>(state: &S, moves: &[Move], cycle: &[Facelet], depth: u8) -> u64
+where
+    S: SearchState,
+{
+    for &position in cycle {
+        black_box(state.read(position));
+    }
+
+    match depth {
+        0 => 1,
+        _ => moves.iter().fold(1, |nodes, &m| {
+            nodes + walk(&state.next(m), moves, cycle, depth - 1)
+        }),
+    }
+}
+
+fn search(c: &mut Criterion) {
+    let moves = [MoveKind::R, MoveKind::U, MoveKind::D]
+        .iter()
+        .flat_map(MoveKind::to_moves)
+        .collect::<Vec<_>>();
+    let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD).to_facelets();
+    let depth = 4;
+    let nodes = walk(&CubieCube::default(), &moves, &cycle, depth);
+    let mut group = c.benchmark_group("search");
+
+    group.throughput(Throughput::Elements(nodes));
+
+    group.bench_function("facelet", |b| {
+        b.iter(|| walk(&FaceletCube::default(), &moves, &cycle, depth))
+    });
+
+    group.bench_function("cubie", |b| {
+        b.iter(|| walk(&CubieCube::default(), &moves, &cycle, depth))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, apply_moves, conversions, search);
+criterion_main!(benches);
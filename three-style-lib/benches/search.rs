@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use three_style_lib::{
+    commutator::{finder::CommutatorSearch, types::Cycle},
+    moves::MoveKind,
+    sticker::{Corner, Edge, Sticker},
+};
+
+/// Measures the number of setup nodes visited per second.
+fn bench_search<T>(c: &mut Criterion, name: &str, search: CommutatorSearch<T>)
+where
+    T: Sticker,
+{
+    let (_, stats) = search.find_all_with_stats();
+    let mut group = c.benchmark_group("search");
+
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(stats.nodes));
+    group.bench_function(name, |b| b.iter(|| search.find_all()));
+    group.finish();
+}
+
+fn corners(c: &mut Criterion) {
+    let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+    let moves = [MoveKind::R, MoveKind::U, MoveKind::D];
+
+    bench_search(c, "corners_rud_7", CommutatorSearch::new(cycle, &moves, 7));
+}
+
+fn edges(c: &mut Criterion) {
+    let cycle = Cycle::new(Edge::UF, Edge::UB, Edge::LF);
+    let moves = [MoveKind::R, MoveKind::U, MoveKind::M];
+
+    bench_search(c, "edges_rum_6", CommutatorSearch::new(cycle, &moves, 6));
+}
+
+criterion_group!(benches, corners, edges);
+criterion_main!(benches);
@@ -1,2 +1,4 @@
 pub mod finder;
+pub mod ranking;
+pub mod table;
 pub mod types;
@@ -0,0 +1,251 @@
+use super::types::Commutator;
+use crate::moves::{ErgonomicScorer, Metric};
+use std::cmp::Ordering;
+
+/// Sort key of a commutator, made of values compared in order, lowest first.
+#[derive(Debug, Clone, Default)]
+pub struct RankKey(Vec<f64>);
+
+impl RankKey {
+    pub fn new(value: f64) -> Self {
+        Self(vec![value])
+    }
+
+    /// Uses the values of the other key for commutators with equal values.
+    pub fn then(mut self, other: RankKey) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+impl Ord for RankKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl PartialOrd for RankKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for RankKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for RankKey {}
+
+/// Ordering criterion for commutators, rankers can be chained to break ties.
+/// Example: `PureFirst.then(ReducedLength(Metric::Htm))`
+pub trait Ranker {
+    /// Returns the sort key of the commutator, computed once per commutator by [`rank`].
+    fn key(&self, commutator: &Commutator) -> RankKey;
+
+    /// Uses the other ranker for commutators considered equal by this one.
+    fn then<R>(self, other: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+        R: Ranker,
+    {
+        Chain(self, other)
+    }
+}
+
+/// Ranks by the length of the reduced expanded algorithm in the given metric, shortest first.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReducedLength(pub Metric);
+
+impl Ranker for ReducedLength {
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        RankKey::new(commutator.expand().reduce().length(self.0) as f64)
+    }
+}
+
+/// Ranks by the number of setup moves, shortest first.
+#[derive(Debug, Clone, Copy)]
+pub struct SetupLength;
+
+impl Ranker for SetupLength {
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        RankKey::new(commutator.setup.as_ref().map_or(0, |s| s.len()) as f64)
+    }
+}
+
+/// Ranks pure commutators before the ones with setup moves.
+#[derive(Debug, Clone, Copy)]
+pub struct PureFirst;
+
+impl Ranker for PureFirst {
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        RankKey::new(if commutator.is_pure() { 0.0 } else { 1.0 })
+    }
+}
+
+/// Ranks by the ergonomic score of the reduced expanded algorithm, most comfortable first.
+impl Ranker for ErgonomicScorer {
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        RankKey::new(self.score(&commutator.expand().reduce()))
+    }
+}
+
+/// Ranks using a custom scoring function, lowest score first.
+#[derive(Debug, Clone, Copy)]
+pub struct Score<F>(pub F);
+
+impl<F> Ranker for Score<F>
+where
+    F: Fn(&Commutator) -> f64,
+{
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        RankKey::new((self.0)(commutator))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<A, B>(A, B);
+
+impl<A, B> Ranker for Chain<A, B>
+where
+    A: Ranker,
+    B: Ranker,
+{
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        self.0.key(commutator).then(self.1.key(commutator))
+    }
+}
+
+impl<R> Ranker for [R]
+where
+    R: Ranker,
+{
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        self.iter()
+            .fold(RankKey::default(), |key, r| key.then(r.key(commutator)))
+    }
+}
+
+impl<R> Ranker for Box<R>
+where
+    R: Ranker + ?Sized,
+{
+    fn key(&self, commutator: &Commutator) -> RankKey {
+        (**self).key(commutator)
+    }
+}
+
+/// Sorts the commutators from best to worst, equally ranked commutators keep their order.
+pub fn rank<R>(commutators: &mut [Commutator], ranker: &R)
+where
+    R: Ranker + ?Sized,
+{
+    commutators.sort_by_cached_key(|c| ranker.key(c));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn commutators(inputs: &[&str]) -> Vec<Commutator> {
+        inputs
+            .iter()
+            .map(|s| Commutator::from_str(s).unwrap())
+            .collect()
+    }
+
+    fn strings(commutators: &[Commutator]) -> Vec<String> {
+        commutators.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rankers() {
+        let inputs = [
+            "[R U: [R' U' R, D]]",
+            "[D: [R' D' R, U]]",
+            "[R' D' R, U2]",
+            "[U' D2: [R D' R', U2]]",
+        ];
+        let mut results = commutators(&inputs);
+
+        rank(&mut results, &ReducedLength::default());
+        assert_eq!(
+            vec![
+                "[R' D' R, U2]",
+                "[D: [R' D' R, U]]",
+                "[U' D2: [R D' R', U2]]",
+                "[R U: [R' U' R, D]]",
+            ],
+            strings(&results)
+        );
+
+        rank(
+            &mut results,
+            &PureFirst.then(SetupLength).then(ReducedLength::default()),
+        );
+        assert_eq!(
+            vec![
+                "[R' D' R, U2]",
+                "[D: [R' D' R, U]]",
+                "[U' D2: [R D' R', U2]]",
+                "[R U: [R' U' R, D]]",
+            ],
+            strings(&results)
+        );
+
+        let longest = |c: &Commutator| -(c.expand().reduce().len() as f64);
+
+        rank(&mut results, &Score(longest));
+        assert_eq!("[R U: [R' U' R, D]]", results[0].to_string());
+        assert_eq!("[R' D' R, U2]", results[3].to_string());
+    }
+
+    #[test]
+    fn test_rank_key() {
+        let key = |values: &[f64]| RankKey(values.to_vec());
+
+        assert!(key(&[1.0, 5.0]) < key(&[2.0, 0.0]));
+        assert!(key(&[1.0, 0.0]) < key(&[1.0, 5.0]));
+        assert!(key(&[-1.0]) < key(&[0.0, 0.0]));
+        assert_eq!(key(&[1.0]), RankKey::new(1.0).then(RankKey::default()));
+    }
+
+    #[test]
+    fn test_ergonomic_ranking() {
+        let mut results = commutators(&["[B' U' B, D]", "[L' D' L, U]", "[R' D' R, U]"]);
+
+        rank(&mut results, &ErgonomicScorer::default());
+        assert_eq!(
+            vec!["[R' D' R, U]", "[L' D' L, U]", "[B' U' B, D]"],
+            strings(&results)
+        );
+    }
+
+    #[test]
+    fn test_ranker_list() {
+        let mut results = commutators(&[
+            "[U' D2: [R D' R', U2]]",
+            "[D: [R' D' R, U]]",
+            "[R' D' R, U2]",
+        ]);
+        let rankers: Vec<Box<dyn Ranker>> =
+            vec![Box::new(SetupLength), Box::new(ReducedLength::default())];
+
+        rank(&mut results, rankers.as_slice());
+        assert_eq!(
+            vec![
+                "[R' D' R, U2]",
+                "[D: [R' D' R, U]]",
+                "[U' D2: [R D' R', U2]]"
+            ],
+            strings(&results)
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use super::{
+    finder::CommutatorSearch,
+    types::{Commutator, Cycle},
+};
+use crate::{
+    cubie::CubieCube,
+    facelet::Facelet,
+    moves::{Alg, Move, MoveKind},
+    sticker::Sticker,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    marker::PhantomData,
+};
+
+/// Maximum length of a pure commutator, an interchange and a 3 moves insertion.
+const PURE_MAX_DEPTH: u8 = 4;
+
+/// Identifies a 3-cycle of pieces, see [`case_key`].
+pub(crate) type CaseKey = [Facelet; 3];
+
+/// Returns the rotations of the cycle and of its other stickers,
+/// which all describe the same case (e.g. `UFR UBL RFD` and `RUF LUB DRF`).
+pub(crate) fn case_cycles<T>(cycle: Cycle<T>) -> impl Iterator<Item = Cycle<T>>
+where
+    T: Sticker,
+{
+    let [first, second, third] = [cycle.first(), cycle.second(), cycle.third()];
+
+    (0..T::SIZE).flat_map(move |n| {
+        let [a, b, c] = [first.rotate(n), second.rotate(n), third.rotate(n)];
+        [
+            Cycle::new(a, b, c),
+            Cycle::new(b, c, a),
+            Cycle::new(c, a, b),
+        ]
+    })
+}
+
+/// Returns the lowest facelets among the cycles describing the same case.
+pub(crate) fn case_key<T>(cycle: Cycle<T>) -> CaseKey
+where
+    T: Sticker,
+{
+    case_cycles(cycle)
+        .map(|c| c.to_facelets())
+        .min_by_key(|facelets| facelets.map(|f| f as usize))
+        .unwrap()
+}
+
+/// Commutators of every 3-cycle of a piece type for a fixed moveset.
+/// Pure commutators are searched once for each case and then carried to the other cases
+/// by setup moves explored in breadth first order, a 3-cycle is then answered by a lookup
+/// with the shortest setup reaching it.
+/// Example:
+/// ```
+/// use three_style_lib::{
+///     commutator::{table::CaseTable, types::Cycle},
+///     moves::MoveKind,
+///     sticker::Corner,
+/// };
+///
+/// let table = CaseTable::build(&[MoveKind::R, MoveKind::U, MoveKind::D], 6);
+/// let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+///
+/// assert!(table.get(cycle).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaseTable<T> {
+    entries: HashMap<CaseKey, Commutator>,
+    marker: PhantomData<T>,
+}
+
+impl<T> CaseTable<T>
+where
+    T: Sticker,
+{
+    /// Builds the table with the depth constraint of the search, among the commutators with
+    /// the shortest setup a case keeps the one with the shortest reduced expanded algorithm.
+    pub fn build(allowed_moves: &[MoveKind], max_depth: u8) -> Self {
+        let moves = allowed_moves
+            .iter()
+            .flat_map(MoveKind::to_moves)
+            .collect::<Vec<_>>();
+        let stickers = movable_stickers::<T>(&moves);
+        let pure = pure_commutators(&stickers, allowed_moves, max_depth);
+        let min_len = pure.iter().map(|(_, c)| c.len()).min().unwrap_or(0);
+        let max_setup = (max_depth as usize).saturating_sub(min_len);
+        let mut best = pure
+            .iter()
+            .map(|(cycle, c)| (case_key(*cycle), (c.expand().reduce().len(), c.clone())))
+            .collect::<HashMap<_, _>>();
+        let mut visited = HashSet::from([CubieCube::default().key()]);
+        let mut level = vec![(CubieCube::default(), Vec::new())];
+
+        for depth in 1..=max_setup {
+            if best.len() == case_count::<T>(stickers.len()) {
+                break;
+            }
+
+            let mut found = HashMap::new();
+
+            level = next_setups(&level, &moves, &mut visited);
+
+            for (state, setup) in &level {
+                for (cycle, pure) in &pure {
+                    if depth + pure.len() > max_depth as usize {
+                        continue;
+                    }
+
+                    // `[S: P]` cycles the stickers which S brings to the positions cycled by P
+                    let Some(case) = setup_case(state, *cycle) else {
+                        continue;
+                    };
+
+                    if best.contains_key(&case) {
+                        continue;
+                    }
+
+                    let commutator = Commutator {
+                        setup: Some(Alg::new(setup.clone())),
+                        ..pure.clone()
+                    };
+                    let length = commutator.expand().reduce().len();
+
+                    match found.entry(case) {
+                        Entry::Vacant(entry) => {
+                            entry.insert((length, commutator));
+                        }
+                        Entry::Occupied(mut entry) if length < entry.get().0 => {
+                            entry.insert((length, commutator));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            best.extend(found);
+        }
+
+        Self {
+            entries: best.into_iter().map(|(key, (_, c))| (key, c)).collect(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self, cycle: Cycle<T>) -> Option<&Commutator> {
+        self.entries.get(&case_key(cycle))
+    }
+
+    /// Returns the number of cases having a commutator.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Returns the stickers moved by at least one of the moves, the other ones can't be cycled.
+pub(crate) fn movable_stickers<T>(moves: &[Move]) -> Vec<T>
+where
+    T: Sticker,
+{
+    let states = moves
+        .iter()
+        .map(|&m| CubieCube::default().apply_move(m))
+        .collect::<Vec<_>>();
+
+    T::ALL
+        .into_iter()
+        .filter(|s| {
+            let facelet = s.as_facelet();
+            states.iter().any(|state| state.facelet(facelet) != facelet)
+        })
+        .collect()
+}
+
+/// Returns the number of distinct 3-cycles between the given number of stickers.
+fn case_count<T>(stickers: usize) -> usize
+where
+    T: Sticker,
+{
+    let pieces = stickers / T::SIZE;
+
+    match pieces {
+        0..=2 => 0,
+        _ => pieces * (pieces - 1) * (pieces - 2) / 3 * T::SIZE.pow(2),
+    }
+}
+
+fn setup_case<T>(state: &CubieCube, cycle: Cycle<T>) -> Option<CaseKey>
+where
+    T: Sticker,
+{
+    let [first, second, third] = cycle
+        .to_facelets()
+        .map(|f| T::from_facelet(state.facelet(f)));
+
+    Some(case_key(Cycle::new(first?, second?, third?)))
+}
+
+/// Searches the best pure commutator of every case.
+fn pure_commutators<T>(
+    stickers: &[T],
+    allowed_moves: &[MoveKind],
+    max_depth: u8,
+) -> Vec<(Cycle<T>, Commutator)>
+where
+    T: Sticker,
+{
+    let max_depth = max_depth.min(PURE_MAX_DEPTH);
+
+    cases(stickers)
+        .filter_map(|cycle| {
+            let commutator = search_case(cycle, allowed_moves, max_depth, 1)
+                .filter(Commutator::is_pure)
+                .min_by_key(|c| c.expand().reduce().len())?;
+
+            Some((cycle, commutator))
+        })
+        .collect()
+}
+
+/// Returns one cycle of every case between the stickers, cases are enumerated
+/// in the order of the stickers and the first cycle of each one is kept.
+pub(crate) fn cases<T>(stickers: &[T]) -> impl Iterator<Item = Cycle<T>> + '_
+where
+    T: Sticker,
+{
+    let mut seen = HashSet::new();
+
+    stickers
+        .iter()
+        .flat_map(move |&first| {
+            stickers
+                .iter()
+                .filter(move |s| !s.is_same_piece(&first))
+                .flat_map(move |&second| {
+                    stickers
+                        .iter()
+                        .filter(move |s| !s.is_same_piece(&first) && !s.is_same_piece(&second))
+                        .map(move |&third| Cycle::new(first, second, third))
+                })
+        })
+        .filter(move |&cycle| seen.insert(case_key(cycle)))
+}
+
+/// Searches the commutators of every cycle describing the case of the cycle.
+pub(crate) fn search_case<'a, T>(
+    cycle: Cycle<T>,
+    allowed_moves: &'a [MoveKind],
+    max_depth: u8,
+    threads: usize,
+) -> impl Iterator<Item = Commutator> + 'a
+where
+    T: Sticker + 'a,
+{
+    // the interchange checks depend on the order of the tracked stickers
+    case_cycles(cycle).flat_map(move |c| {
+        CommutatorSearch::new(c, allowed_moves, max_depth)
+            .threads(threads)
+            .find_all()
+    })
+}
+
+/// Returns the setups one move longer reaching new states,
+/// moves following a move of the same kind or a greater parallel kind are skipped.
+fn next_setups(
+    level: &[(CubieCube, Vec<Move>)],
+    moves: &[Move],
+    visited: &mut HashSet<u128>,
+) -> Vec<(CubieCube, Vec<Move>)> {
+    let mut next = Vec::new();
+
+    for (state, setup) in level {
+        for &m in moves {
+            if let Some(last) = setup.last() {
+                if last.kind == m.kind
+                    || (m.kind < last.kind && last.kind.parallel().contains(&m.kind))
+                {
+                    continue;
+                }
+            }
+
+            let state = state.apply_move(m);
+
+            if visited.insert(state.key()) {
+                let mut setup = setup.clone();
+                setup.push(m);
+                next.push((state, setup));
+            }
+        }
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        facelet::FaceletCube,
+        sheet::generator::sheet_cycles,
+        sticker::{Corner, Edge},
+    };
+
+    fn check_table<T: Sticker>(buffer: T, allowed_moves: &[MoveKind], max_depth: u8) {
+        let table = CaseTable::<T>::build(allowed_moves, max_depth);
+
+        for cycle in sheet_cycles(buffer) {
+            let expected = CommutatorSearch::new(cycle, allowed_moves, max_depth)
+                .find_all()
+                .into_iter()
+                .map(|c| c.expand().reduce().len())
+                .min();
+            let commutator = table.get(cycle);
+            let length = commutator.map(|c| c.expand().reduce().len());
+
+            // the table can also answer cases missed by the search
+            assert!(expected.is_none() || length.is_some());
+
+            if let Some(commutator) = commutator {
+                assert!(commutator.len() <= max_depth as usize);
+                assert_eq!(
+                    FaceletCube::try_from(cycle).unwrap(),
+                    FaceletCube::default().apply_alg(&commutator.expand())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_case_key() {
+        let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+        let rotated = Cycle::new(Corner::UBL, Corner::RFD, Corner::UFR);
+        let inverse = Cycle::new(Corner::UFR, Corner::RFD, Corner::UBL);
+
+        let twisted = Cycle::new(Corner::RUF, Corner::LUB, Corner::DRF);
+
+        assert_eq!(case_key(cycle), case_key(rotated));
+        assert_eq!(case_key(cycle), case_key(twisted));
+        assert_ne!(case_key(cycle), case_key(inverse));
+
+        for cycle in sheet_cycles(Edge::UF) {
+            let [first, second, third] = [cycle.first(), cycle.second(), cycle.third()];
+            let flipped = Cycle::new(second.rotate(1), third.rotate(1), first.rotate(1));
+
+            assert_eq!(case_key(cycle), case_key(flipped));
+            assert_eq!(
+                FaceletCube::try_from(cycle).unwrap(),
+                FaceletCube::try_from(flipped).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_case_count() {
+        let corners = movable_stickers::<Corner>(&MoveKind::R.to_moves());
+        let edges = movable_stickers::<Edge>(&MoveKind::M.to_moves());
+
+        assert_eq!(1008, case_count::<Corner>(Corner::ALL.len()));
+        assert_eq!(1760, case_count::<Edge>(Edge::ALL.len()));
+        assert_eq!(12, corners.len());
+        assert_eq!(8, edges.len());
+        assert_eq!(72, case_count::<Corner>(corners.len()));
+        assert_eq!(32, case_count::<Edge>(edges.len()));
+    }
+
+    #[test]
+    fn test_corner_table() {
+        check_table(Corner::UFR, &[MoveKind::R, MoveKind::U, MoveKind::D], 6);
+    }
+
+    #[test]
+    fn test_edge_table() {
+        check_table(Edge::UF, &[MoveKind::U, MoveKind::R, MoveKind::M], 5);
+    }
+}
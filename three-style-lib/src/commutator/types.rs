@@ -1,9 +1,10 @@
 use crate::{
     error::Error,
     facelet::{Facelet, FaceletTarget},
-    moves::{Alg, Inverse, Move},
+    moves::{parse_bracket, Alg, Inverse, Move, MoveCount, Notation},
+    sticker::{Corner, Edge, Sticker},
 };
-use std::fmt;
+use std::{fmt, ops::Not, str::FromStr};
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(clippy::len_without_is_empty)]
@@ -40,6 +41,95 @@ impl Commutator {
             _ => middle,
         }
     }
+
+    /// Returns an equivalent commutator in a canonical form.
+    /// The setup is reduced, which also sorts parallel moves, then its last moves are absorbed
+    /// by the commutator while possible using `[X': [X, A]] = [A, X']`
+    /// and `[W': [W A W', B]] = [A, W' B W]`.
+    /// Example: `[U2 D' U' F': [D', F U2 F']]` -> `[U D': [F' D' F, U2]]`
+    pub fn canonical(&self) -> Self {
+        let mut setup = self
+            .setup
+            .clone()
+            .map(Alg::reduce)
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut commutator = Commutator {
+            setup: None,
+            ..self.clone()
+        };
+
+        // moves of the last parallel group commute so each of them can be absorbed
+        'outer: while let Some(last) = setup.last().copied() {
+            let group_start = setup
+                .iter()
+                .rposition(|m| m.kind != last.kind && !last.kind.parallel().contains(&m.kind))
+                .map_or(0, |i| i + 1);
+
+            for index in (group_start..setup.len()).rev() {
+                if let Some(absorbed) = commutator.absorb(setup[index]) {
+                    setup.remove(index);
+                    commutator = absorbed;
+                    continue 'outer;
+                }
+            }
+
+            break;
+        }
+
+        Commutator {
+            setup: setup.is_empty().not().then(|| Alg::new(setup)),
+            ..commutator
+        }
+    }
+
+    /// Finds which operand is the interchange, `None` if both have several moves.
+    fn from_operands(first: Alg, second: Alg) -> Option<Self> {
+        let is_double = |alg: &Alg| alg.iter().all(|m| m.count == MoveCount::Double);
+        let insertion_first = match (first.len(), second.len()) {
+            (1, 1) => !is_double(&first) && is_double(&second), // four movers
+            (1, _) => false,
+            (_, 1) => true,
+            _ => return None,
+        };
+        let (insertion, interchange) = match insertion_first {
+            true => (first, second),
+            false => (second, first),
+        };
+
+        Some(Commutator {
+            setup: None,
+            interchange: interchange.into_iter().next()?,
+            insertion,
+            insertion_first,
+        })
+    }
+
+    /// Moves the given setup move inside the commutator if it can be done without adding moves.
+    fn absorb(&self, setup: Move) -> Option<Self> {
+        let insertion = self.insertion.iter().copied().collect::<Vec<_>>();
+
+        if setup == self.interchange.inverse() {
+            return Some(Commutator {
+                interchange: setup,
+                insertion_first: !self.insertion_first,
+                ..self.clone()
+            });
+        }
+
+        match insertion[..] {
+            [first, middle, last] if setup == first.inverse() && last == setup => {
+                Some(Commutator {
+                    setup: None,
+                    interchange: middle,
+                    insertion: Alg::new([setup, self.interchange, first]),
+                    insertion_first: !self.insertion_first,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Commutator {
@@ -62,6 +152,48 @@ impl fmt::Display for Commutator {
     }
 }
 
+impl FromStr for Commutator {
+    type Err = Error;
+
+    /// Parses a pure commutator `[A, B]` or a conjugate `[S: [A, B]]`,
+    /// nested setups are flattened into a single setup sequence.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |position: usize| Error::InvalidCommutator(s.to_owned(), position + 1);
+        let (mut node, operands) = parse_bracket(s).map_err(|e| match e {
+            Error::InvalidAlg(_, column) => Error::InvalidCommutator(s.to_owned(), column),
+            e => e,
+        })?;
+        let mut setup = Alg::default();
+        let mut bracket = 0;
+
+        loop {
+            let (first_start, second_start) = operands[bracket];
+
+            match node {
+                Notation::Conjugate(a, b) => {
+                    if !matches!(*b, Notation::Commutator(..) | Notation::Conjugate(..)) {
+                        return Err(error(second_start));
+                    }
+
+                    bracket += 1 + a.bracket_count();
+                    setup = setup + a.evaluate();
+                    node = *b;
+                }
+                Notation::Commutator(a, b) => {
+                    let commutator = Commutator::from_operands(a.evaluate(), b.evaluate())
+                        .ok_or_else(|| error(first_start))?;
+
+                    return Ok(Commutator {
+                        setup: setup.is_empty().not().then_some(setup),
+                        ..commutator
+                    });
+                }
+                _ => unreachable!(), // only brackets are parsed
+            }
+        }
+    }
+}
+
 /// Wrapper around 3-cycle targets (stickers).
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Cycle<T> {
@@ -120,6 +252,114 @@ where
     }
 }
 
+/// Pair of corners twisted in place, the targets are the stickers
+/// where the reference (U/D) sticker of each corner is carried to.
+/// Example: `FRU - BUR` twists UFR clockwise and URB counterclockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Twist {
+    targets: [Corner; 2],
+}
+
+impl Twist {
+    pub fn new(first: Corner, second: Corner) -> Self {
+        Self {
+            targets: [first, second],
+        }
+    }
+
+    pub fn first(&self) -> Corner {
+        self.targets[0]
+    }
+
+    pub fn second(&self) -> Corner {
+        self.targets[1]
+    }
+}
+
+impl Inverse for Twist {
+    fn inverse(&self) -> Self {
+        Self {
+            targets: self
+                .targets
+                .map(|t| t.reference().rotate(Corner::SIZE - t.orientation())),
+        }
+    }
+}
+
+impl fmt::Display for Twist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.first(), self.second())
+    }
+}
+
+/// Pair of edges flipped in place, the targets are the stickers
+/// where the reference (U/D or F/B) sticker of each edge is carried to.
+/// Example: `FU - BU` flips UF and UB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flip {
+    targets: [Edge; 2],
+}
+
+impl Flip {
+    pub fn new(first: Edge, second: Edge) -> Self {
+        Self {
+            targets: [first, second],
+        }
+    }
+
+    pub fn first(&self) -> Edge {
+        self.targets[0]
+    }
+
+    pub fn second(&self) -> Edge {
+        self.targets[1]
+    }
+}
+
+impl Inverse for Flip {
+    fn inverse(&self) -> Self {
+        *self
+    }
+}
+
+impl fmt::Display for Flip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} - {}", self.first(), self.second())
+    }
+}
+
+/// Commutators applied one after another, used for cases
+/// that can't be solved with a single commutator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommutatorSequence(pub Vec<Commutator>);
+
+impl CommutatorSequence {
+    /// Returns the non-reduced expanded algorithm.
+    pub fn expand(&self) -> Alg {
+        self.0
+            .iter()
+            .fold(Alg::default(), |acc, c| acc + c.expand())
+    }
+
+    /// Returns an iterator over the commutators.
+    pub fn iter(&self) -> impl Iterator<Item = &Commutator> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for CommutatorSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self
+            .0
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        write!(f, "{s}")
+    }
+}
+
 pub trait ThreeCycle: Sized {
     fn cycle<T>(self, cycle: Cycle<T>) -> Result<Self, Error>
     where
@@ -128,8 +368,14 @@ pub trait ThreeCycle: Sized {
 
 #[cfg(test)]
 mod tests {
-    use super::Commutator;
-    use crate::{alg, moves::Move};
+    use super::{Commutator, Twist};
+    use crate::{
+        alg,
+        error::Error,
+        facelet::FaceletCube,
+        moves::{Inverse, Move},
+        sticker::Corner,
+    };
     use std::str::FromStr;
 
     #[test]
@@ -167,4 +413,130 @@ mod tests {
 
         assert_eq!(expected, commutator.expand().reduce());
     }
+
+    #[test]
+    fn test_commutator_canonical() {
+        let cases = [
+            ("[U2 D' U' F': [D', F U2 F']]", "[U D': [F' D' F, U2]]"),
+            ("[D' U: [F' D' F, U2]]", "[U D': [F' D' F, U2]]"),
+            ("[D F D: [D', R' U R]]", "[D F: [R' U R, D]]"),
+            ("[R' U D: [D', R' U' R]]", "[R' U: [R' U' R, D]]"),
+            ("[U' F' R': [D, R U R']]", "[U' F': [R' D R, U]]"),
+            ("[R U R': [U', R D R']]", "[R U: [R' U' R, D]]"),
+            ("[D: [R' D' R, U]]", "[D: [R' D' R, U]]"),
+            ("[R' R: [U, R' D R]]", "[U, R' D R]"),
+        ];
+
+        for (input, expected) in cases {
+            let commutator = Commutator::from_str(input).unwrap();
+            let canonical = commutator.canonical();
+            let state = FaceletCube::default();
+
+            assert_eq!(expected, canonical.to_string());
+            assert_eq!(
+                state.apply_alg(&commutator.expand()),
+                state.apply_alg(&canonical.expand())
+            );
+        }
+    }
+
+    #[test]
+    fn test_commutator_parsing() {
+        let commutator = Commutator::from_str("[U, R' D' R]").unwrap();
+        let expected = Commutator {
+            setup: None,
+            interchange: Move::from_str("U").unwrap(),
+            insertion: alg!("R' D' R"),
+            insertion_first: false,
+        };
+
+        assert_eq!(expected, commutator);
+
+        let commutator = Commutator::from_str("[D: [R' D' R, U]]").unwrap();
+        let expected = Commutator {
+            setup: Some(alg!("D")),
+            interchange: Move::from_str("U").unwrap(),
+            insertion: alg!("R' D' R"),
+            insertion_first: true,
+        };
+
+        assert_eq!(expected, commutator);
+
+        let commutator = Commutator::from_str("[R: [U' : [R' D R, U2]]]").unwrap();
+        let expected = Commutator {
+            setup: Some(alg!("R U'")),
+            interchange: Move::from_str("U2").unwrap(),
+            insertion: alg!("R' D R"),
+            insertion_first: true,
+        };
+
+        assert_eq!(expected, commutator);
+
+        let commutator = Commutator::from_str("[(R U)2: [D, R']]").unwrap();
+        let expected = Commutator {
+            setup: Some(alg!("R U R U")),
+            interchange: Move::from_str("D").unwrap(),
+            insertion: alg!("R'"),
+            insertion_first: false,
+        };
+
+        assert_eq!(expected, commutator);
+
+        let commutator = Commutator::from_str("[M', U2]").unwrap();
+        let expected = Commutator {
+            setup: None,
+            interchange: Move::from_str("U2").unwrap(),
+            insertion: alg!("M'"),
+            insertion_first: true,
+        };
+
+        assert_eq!(expected, commutator);
+    }
+
+    #[test]
+    fn test_commutator_round_trip() {
+        let inputs = [
+            "[U, R' D' R]",
+            "[R' D' R, U]",
+            "[D: [R' D' R, U]]",
+            "[U R: [D', R U' R']]",
+            "[M', U2]",
+            "[U2, M']",
+        ];
+
+        for input in inputs {
+            let commutator = Commutator::from_str(input).unwrap();
+
+            assert_eq!(input, commutator.to_string());
+            assert_eq!(Ok(commutator.clone()), commutator.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn test_invalid_commutator() {
+        let error = |s: &str, column| Err(Error::InvalidCommutator(s.to_owned(), column));
+
+        assert_eq!(error("U, R", 1), Commutator::from_str("U, R"));
+        assert_eq!(error("[U, R", 6), Commutator::from_str("[U, R"));
+        assert_eq!(error("[U, R X]", 7), Commutator::from_str("[U, R X]"));
+        assert_eq!(error("[R U, R D]", 2), Commutator::from_str("[R U, R D]"));
+        assert_eq!(error("[: [U, R]]", 2), Commutator::from_str("[: [U, R]]"));
+        assert_eq!(error("[U, ]", 5), Commutator::from_str("[U, ]"));
+        assert_eq!(error("[U R]", 5), Commutator::from_str("[U R]"));
+        assert_eq!(error("[U, R] U", 8), Commutator::from_str("[U, R] U"));
+        assert_eq!(error("[U: R]", 5), Commutator::from_str("[U: R]"));
+        assert_eq!(
+            error("[[R, U]: [R U, R D]]", 11),
+            Commutator::from_str("[[R, U]: [R U, R D]]")
+        );
+    }
+
+    #[test]
+    fn test_twist_inverse() {
+        let twist = Twist::new(Corner::FRU, Corner::BUR);
+        let expected = Twist::new(Corner::RUF, Corner::RBU);
+
+        assert_eq!(expected, twist.inverse());
+        assert_eq!(twist, twist.inverse().inverse());
+    }
 }
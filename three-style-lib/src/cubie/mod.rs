@@ -0,0 +1,4 @@
+mod state;
+mod tables;
+
+pub use state::CubieCube;
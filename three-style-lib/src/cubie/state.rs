@@ -0,0 +1,228 @@
+use super::tables::{move_table, Location, CENTER_FACELETS, LOCATIONS};
+use crate::{
+    error::Error,
+    facelet::{Facelet, FaceletCube, FaceletTarget},
+    moves::{Alg, Move},
+    sticker::{Corner, Edge, Sticker, CORNER_FACELET_MAP, EDGE_FACELET_MAP},
+};
+use std::ops::Mul;
+
+/// Number of bits used for the piece index, the orientation is stored above.
+const CORNER_SHIFT: u8 = 3;
+const EDGE_SHIFT: u8 = 4;
+const CORNER_MASK: u8 = (1 << CORNER_SHIFT) - 1;
+const EDGE_MASK: u8 = (1 << EDGE_SHIFT) - 1;
+
+/// State of the cube at the cubie level in the "is replaced by" representation,
+/// each position stores the piece replacing it with its orientation in the upper bits.
+/// Centers are tracked as well for slice moves and rotations.
+/// Moves are applied with precomputed tables, see [`FaceletCube`] for the facelet level.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CubieCube {
+    corners: [u8; 8],
+    edges: [u8; 12],
+    centers: [u8; 6],
+}
+
+impl Default for CubieCube {
+    fn default() -> Self {
+        Self {
+            corners: [0, 1, 2, 3, 4, 5, 6, 7],
+            edges: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+            centers: [0, 1, 2, 3, 4, 5],
+        }
+    }
+}
+
+impl CubieCube {
+    pub fn is_solved(&self) -> bool {
+        FaceletCube::from(self).is_solved()
+    }
+
+    pub fn apply_move(&self, m: Move) -> Self {
+        self * &move_table()[m.index()]
+    }
+
+    pub fn apply_alg(&self, alg: &Alg) -> Self {
+        alg.iter().fold(*self, |acc, m| acc.apply_move(*m))
+    }
+
+    /// Packs the whole state in an integer, used as a compact key for transposition tables.
+    pub fn key(&self) -> u128 {
+        let pieces = self.corners.iter().chain(&self.edges);
+        let key = pieces.fold(0, |key, &cubie| key << 5 | cubie as u128);
+
+        self.centers
+            .iter()
+            .fold(key, |key, &center| key << 3 | center as u128)
+    }
+
+    /// Returns the facelet replacing the given facelet position.
+    pub fn facelet(&self, position: Facelet) -> Facelet {
+        match LOCATIONS[position as usize] {
+            Location::Corner(index, orientation) => {
+                let cubie = self.corners[index];
+                let piece = (cubie & CORNER_MASK) as usize;
+                let sticker = piece * 3 + (cubie >> CORNER_SHIFT) as usize;
+                CORNER_FACELET_MAP[sticker][orientation]
+            }
+            Location::Edge(index, orientation) => {
+                let cubie = self.edges[index];
+                let piece = (cubie & EDGE_MASK) as usize;
+                let sticker = piece * 2 + (cubie >> EDGE_SHIFT) as usize;
+                EDGE_FACELET_MAP[sticker][orientation]
+            }
+            Location::Center(index) => CENTER_FACELETS[self.centers[index] as usize],
+        }
+    }
+}
+
+impl Mul<Self> for &CubieCube {
+    type Output = CubieCube;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut res = CubieCube::default();
+
+        for (i, &cubie) in rhs.corners.iter().enumerate() {
+            let replacing = self.corners[(cubie & CORNER_MASK) as usize];
+            let orientation = (replacing >> CORNER_SHIFT) + (cubie >> CORNER_SHIFT);
+            let orientation = if orientation >= 3 {
+                orientation - 3
+            } else {
+                orientation
+            };
+
+            res.corners[i] = replacing & CORNER_MASK | orientation << CORNER_SHIFT;
+        }
+
+        for (i, &cubie) in rhs.edges.iter().enumerate() {
+            let replacing = self.edges[(cubie & EDGE_MASK) as usize];
+            res.edges[i] = replacing ^ (cubie & !EDGE_MASK);
+        }
+
+        for (i, &center) in rhs.centers.iter().enumerate() {
+            res.centers[i] = self.centers[center as usize];
+        }
+
+        res
+    }
+}
+
+impl From<&CubieCube> for FaceletCube {
+    fn from(value: &CubieCube) -> Self {
+        let solved = FaceletCube::default();
+        let mut cube = FaceletCube::default();
+
+        for i in 0..54 {
+            cube[i] = value.facelet(solved[i]);
+        }
+
+        cube
+    }
+}
+
+impl TryFrom<&FaceletCube> for CubieCube {
+    type Error = Error;
+
+    /// Reads the piece and the orientation from the reference facelet of each position,
+    /// fails if the facelets don't form whole pieces.
+    fn try_from(value: &FaceletCube) -> Result<Self, Self::Error> {
+        let error = || Error::InvalidCubeState(value.to_string());
+        let mut cube = CubieCube::default();
+
+        for position in 0..8 {
+            let facelet = value[Corner::ALL[position * 3].as_facelet()];
+            let sticker = Corner::from_facelet(facelet).ok_or_else(error)?;
+            let orientation = sticker.orientation() as u8;
+            cube.corners[position] = (sticker.index() / 3) as u8 | orientation << CORNER_SHIFT;
+        }
+        for position in 0..12 {
+            let facelet = value[Edge::ALL[position * 2].as_facelet()];
+            let sticker = Edge::from_facelet(facelet).ok_or_else(error)?;
+            let orientation = sticker.orientation() as u8;
+            cube.edges[position] = (sticker.index() / 2) as u8 | orientation << EDGE_SHIFT;
+        }
+        for (position, center) in cube.centers.iter_mut().enumerate() {
+            let facelet = value[CENTER_FACELETS[position]];
+            let index = CENTER_FACELETS.iter().position(|&f| f == facelet);
+            *center = index.ok_or_else(error)? as u8;
+        }
+
+        match FaceletCube::from(&cube) == *value {
+            true => Ok(cube),
+            false => Err(error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alg,
+        commutator::types::Cycle,
+        moves::{Inverse, MoveCount, MoveKind},
+    };
+
+    #[test]
+    fn test_move_tables() {
+        let solved = CubieCube::default();
+
+        for kind in MoveKind::ALL {
+            for m in kind.to_moves() {
+                let expected = FaceletCube::default().apply_move(m);
+                assert_eq!(expected, FaceletCube::from(&solved.apply_move(m)));
+            }
+        }
+
+        let m = Move::new(MoveKind::R, MoveCount::Simple);
+        let four = (0..4).fold(solved, |acc, _| acc.apply_move(m));
+        assert_eq!(solved, four);
+    }
+
+    #[test]
+    fn test_facelet_conversion() {
+        let alg = alg!("R U2 M' F r' D' x S2 B Lw' E y2 Dw z'");
+        let facelet = FaceletCube::default().apply_alg(&alg);
+        let cubie = CubieCube::default().apply_alg(&alg);
+
+        assert_eq!(facelet, FaceletCube::from(&cubie));
+        assert_eq!(Ok(cubie), CubieCube::try_from(&facelet));
+        assert!(!cubie.is_solved());
+        assert!(cubie.apply_alg(&alg.inverse()).is_solved());
+
+        let cycle = Cycle::new(Corner::UFR, Corner::LDF, Corner::BUR);
+        let facelet = FaceletCube::try_from(cycle).unwrap();
+
+        assert_eq!(
+            facelet,
+            FaceletCube::from(&CubieCube::try_from(&facelet).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_state_key() {
+        let cube = CubieCube::default().apply_alg(&alg!("R U D' M x"));
+
+        assert_eq!(cube.key(), cube.apply_alg(&alg!("U U'")).key());
+        assert_ne!(
+            cube.key(),
+            cube.apply_move(Move::new(MoveKind::R, MoveCount::Simple))
+                .key()
+        );
+        assert_ne!(cube.key(), cube.apply_alg(&alg!("y")).key());
+    }
+
+    #[test]
+    fn test_invalid_state() {
+        let mut state = FaceletCube::default();
+
+        state[Facelet::U8] = Facelet::R0;
+        state[Facelet::R0] = Facelet::U8;
+
+        assert_eq!(
+            Err(Error::InvalidCubeState(state.to_string())),
+            CubieCube::try_from(&state)
+        );
+    }
+}
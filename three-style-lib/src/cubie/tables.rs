@@ -0,0 +1,64 @@
+use super::state::CubieCube;
+use crate::{
+    facelet::{Facelet, FaceletCube},
+    moves::Move,
+    sticker::{CORNER_FACELET_MAP, EDGE_FACELET_MAP},
+};
+use std::sync::OnceLock;
+
+/// Center facelets in the `URFDLB` order.
+pub const CENTER_FACELETS: [Facelet; 6] = [
+    Facelet::U4,
+    Facelet::R4,
+    Facelet::F4,
+    Facelet::D4,
+    Facelet::L4,
+    Facelet::B4,
+];
+
+/// Cubie position and orientation of a facelet position.
+#[derive(Debug, Clone, Copy)]
+pub enum Location {
+    Corner(usize, usize),
+    Edge(usize, usize),
+    Center(usize),
+}
+
+/// Cubie location of every facelet position, computed at compile time from the sticker maps.
+pub static LOCATIONS: [Location; 54] = locations();
+
+const fn locations() -> [Location; 54] {
+    let mut locations = [Location::Center(0); 54];
+    let mut index = 0;
+
+    while index < 24 {
+        locations[CORNER_FACELET_MAP[index][0] as usize] = Location::Corner(index / 3, index % 3);
+        locations[EDGE_FACELET_MAP[index][0] as usize] = Location::Edge(index / 2, index % 2);
+        index += 1;
+    }
+
+    index = 0;
+
+    while index < CENTER_FACELETS.len() {
+        locations[CENTER_FACELETS[index] as usize] = Location::Center(index);
+        index += 1;
+    }
+
+    locations
+}
+
+/// Returns the cubie cube of every move in the [`Move::index`] order.
+pub fn move_table() -> &'static [CubieCube; Move::COUNT] {
+    static MOVES: OnceLock<[CubieCube; Move::COUNT]> = OnceLock::new();
+
+    MOVES.get_or_init(|| {
+        let mut moves = [CubieCube::default(); Move::COUNT];
+
+        for m in Move::all() {
+            moves[m.index()] = CubieCube::try_from(&FaceletCube::from(m))
+                .expect("move cubes should be valid cube states");
+        }
+
+        moves
+    })
+}
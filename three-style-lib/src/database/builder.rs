@@ -0,0 +1,184 @@
+use super::format::{
+    case_bytes, moveset, write_commutator, PieceType, ENTRY_SIZE, HEADER_SIZE, MAGIC, SECTION_SIZE,
+    VERSION,
+};
+use crate::{
+    commutator::{
+        table::{case_key, cases, movable_stickers, search_case},
+        types::{Commutator, Cycle},
+    },
+    error::Error,
+    moves::MoveKind,
+    sticker::Sticker,
+};
+use std::{collections::HashSet, fs, path::Path};
+
+#[derive(Debug, Clone)]
+struct Section {
+    piece: PieceType,
+    moveset: u32,
+    max_depth: u8,
+    entries: Vec<([u8; 3], Vec<Commutator>)>,
+}
+
+/// Searches the best commutators of every case for the added movesets,
+/// the result is written in the binary format read by [`super::Database`].
+/// Example:
+/// ```
+/// use three_style_lib::{
+///     commutator::types::Cycle,
+///     database::{Database, DatabaseBuilder},
+///     moves::MoveKind,
+///     sticker::Corner,
+/// };
+///
+/// let allowed_moves = [MoveKind::R, MoveKind::U, MoveKind::D];
+/// let bytes = DatabaseBuilder::new(3)
+///     .add::<Corner>(&allowed_moves, 5)
+///     .to_bytes();
+/// let database = Database::from_bytes(bytes).unwrap();
+/// let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+///
+/// assert!(database.get(cycle, &allowed_moves, 5).unwrap().is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DatabaseBuilder {
+    best: usize,
+    threads: usize,
+    sections: Vec<Section>,
+}
+
+impl DatabaseBuilder {
+    /// Keeps the `best` shortest commutators of each case, at most 255.
+    pub fn new(best: usize) -> Self {
+        Self {
+            best: best.clamp(1, u8::MAX as usize),
+            threads: 1,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn threads(self, threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            ..self
+        }
+    }
+
+    /// Searches every case of the piece type reachable by the moveset, commutators are ranked
+    /// by their reduced expanded algorithm then by their length and duplicates are skipped.
+    pub fn add<T>(mut self, allowed_moves: &[MoveKind], max_depth: u8) -> Self
+    where
+        T: Sticker,
+    {
+        let moves = allowed_moves
+            .iter()
+            .flat_map(MoveKind::to_moves)
+            .collect::<Vec<_>>();
+        let stickers = movable_stickers::<T>(&moves);
+        let mut entries = cases(&stickers)
+            .map(|cycle| {
+                let commutators = self.search(cycle, allowed_moves, max_depth);
+
+                (case_bytes(case_key(cycle)), commutators)
+            })
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|(key, _)| *key);
+
+        let section = Section {
+            piece: PieceType::of_size(T::SIZE),
+            moveset: moveset(allowed_moves),
+            max_depth,
+            entries,
+        };
+
+        self.sections.retain(|s| {
+            (s.piece, s.moveset, s.max_depth) != (section.piece, section.moveset, max_depth)
+        });
+        self.sections.push(section);
+        self
+    }
+
+    fn search<T>(
+        &self,
+        cycle: Cycle<T>,
+        allowed_moves: &[MoveKind],
+        max_depth: u8,
+    ) -> Vec<Commutator>
+    where
+        T: Sticker,
+    {
+        let mut commutators =
+            search_case(cycle, allowed_moves, max_depth, self.threads).collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+
+        commutators.sort_by_cached_key(|c| (c.expand().reduce().len(), c.len()));
+        commutators.retain(|c| seen.insert(c.expand().reduce().to_string()));
+        commutators.truncate(self.best);
+        commutators
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut offset = HEADER_SIZE + self.sections.len() * SECTION_SIZE;
+
+        bytes.extend(MAGIC);
+        bytes.extend(VERSION.to_le_bytes());
+        bytes.extend((self.sections.len() as u16).to_le_bytes());
+
+        let contents = self
+            .sections
+            .iter()
+            .map(|section| {
+                let mut index = Vec::with_capacity(section.entries.len() * ENTRY_SIZE);
+                let mut data = Vec::new();
+
+                for (key, commutators) in &section.entries {
+                    index.extend(key);
+                    index.push(commutators.len() as u8);
+                    index.extend((data.len() as u32).to_le_bytes());
+
+                    for commutator in commutators {
+                        write_commutator(commutator, &mut data);
+                    }
+                }
+
+                (index, data)
+            })
+            .collect::<Vec<_>>();
+
+        for (section, (index, data)) in self.sections.iter().zip(&contents) {
+            bytes.push(section.piece as u8);
+            bytes.push(section.max_depth);
+            bytes.extend(0u16.to_le_bytes());
+            bytes.extend(section.moveset.to_le_bytes());
+            bytes.extend((section.entries.len() as u32).to_le_bytes());
+            bytes.extend((offset as u32).to_le_bytes());
+            bytes.extend(((offset + index.len()) as u32).to_le_bytes());
+            offset += index.len() + data.len();
+        }
+
+        for (index, data) in contents {
+            bytes.extend(index);
+            bytes.extend(data);
+        }
+
+        bytes
+    }
+
+    pub fn write<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        fs::write(path, self.to_bytes()).map_err(|_| Error::WriteFile(path.display().to_string()))
+    }
+}
+
+impl Default for DatabaseBuilder {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
@@ -0,0 +1,138 @@
+//! Binary layout of the database, every integer is little endian.
+//!
+//! ```text
+//! header   magic "3STYLEDB", version u16, section count u16
+//! section  piece u8, max depth u8, reserved u16, moveset u32,
+//!          case count u32, index offset u32, data offset u32
+//! index    case facelets [u8; 3], commutator count u8, offset u32 (relative to the data)
+//! data     commutators of each case, see `write_commutator`
+//! ```
+//!
+//! Sections are keyed by piece type, moveset and depth, their index entries are sorted by case.
+
+use crate::{
+    commutator::{table::CaseKey, types::Commutator},
+    error::Error,
+    moves::{Alg, Move, MoveKind},
+};
+
+pub(crate) const MAGIC: &[u8; 8] = b"3STYLEDB";
+pub(crate) const VERSION: u16 = 1;
+pub(crate) const HEADER_SIZE: usize = 12;
+pub(crate) const SECTION_SIZE: usize = 20;
+pub(crate) const ENTRY_SIZE: usize = 8;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum PieceType {
+    Corner = 0,
+    Edge = 1,
+}
+
+impl PieceType {
+    pub(crate) fn of_size(size: usize) -> Self {
+        match size {
+            3 => PieceType::Corner,
+            _ => PieceType::Edge,
+        }
+    }
+}
+
+/// Moveset as a bit set of move kinds, independent of the order of the moves.
+pub(crate) fn moveset(allowed_moves: &[MoveKind]) -> u32 {
+    allowed_moves
+        .iter()
+        .fold(0, |mask, &kind| mask | 1 << kind as u32)
+}
+
+pub(crate) fn case_bytes(key: CaseKey) -> [u8; 3] {
+    key.map(|f| f as u8)
+}
+
+/// Writes the setup length and moves, the interchange, the insertion length and moves,
+/// then `1` if the insertion comes first. Moves are written as their index.
+pub(crate) fn write_commutator(commutator: &Commutator, bytes: &mut Vec<u8>) {
+    let setup = commutator.setup.clone().unwrap_or_default();
+
+    bytes.push(setup.len() as u8);
+    bytes.extend(setup.iter().map(|m| m.index() as u8));
+    bytes.push(commutator.interchange.index() as u8);
+    bytes.push(commutator.insertion.len() as u8);
+    bytes.extend(commutator.insertion.iter().map(|m| m.index() as u8));
+    bytes.push(commutator.insertion_first as u8);
+}
+
+/// Reads a commutator written by [`write_commutator`] and advances the position.
+pub(crate) fn read_commutator(bytes: &[u8], position: &mut usize) -> Result<Commutator, Error> {
+    let mut next = || {
+        let byte = bytes.get(*position).copied();
+        *position += 1;
+        byte.ok_or_else(|| invalid("truncated commutator"))
+    };
+    let mut read_move = |byte: u8| {
+        Move::from_index(byte as usize).ok_or_else(|| invalid(&format!("invalid move {byte}")))
+    };
+
+    let setup_len = next()?;
+    let setup = (0..setup_len)
+        .map(|_| next().and_then(&mut read_move))
+        .collect::<Result<Vec<_>, _>>()?;
+    let interchange = next().and_then(&mut read_move)?;
+    let insertion_len = next()?;
+    let insertion = (0..insertion_len)
+        .map(|_| next().and_then(&mut read_move))
+        .collect::<Result<Vec<_>, _>>()?;
+    let insertion_first = next()? == 1;
+
+    Ok(Commutator {
+        setup: (!setup.is_empty()).then(|| Alg::new(setup)),
+        interchange,
+        insertion: Alg::new(insertion),
+        insertion_first,
+    })
+}
+
+pub(crate) fn read_u16(bytes: &[u8], position: usize) -> Result<u16, Error> {
+    bytes
+        .get(position..position + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| invalid("unexpected end of file"))
+}
+
+pub(crate) fn read_u32(bytes: &[u8], position: usize) -> Result<u32, Error> {
+    bytes
+        .get(position..position + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| invalid("unexpected end of file"))
+}
+
+pub(crate) fn invalid(reason: &str) -> Error {
+    Error::InvalidDatabase(reason.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_commutator_encoding() {
+        let commutators = ["[U' R D: [U2, R D' R']]", "[R' D2 R, U2]", "[M', U2]"]
+            .map(|c| Commutator::from_str(c).unwrap());
+        let mut bytes = Vec::new();
+        let mut position = 0;
+
+        for commutator in &commutators {
+            write_commutator(commutator, &mut bytes);
+        }
+        for commutator in &commutators {
+            assert_eq!(
+                Ok(commutator.clone()),
+                read_commutator(&bytes, &mut position)
+            );
+        }
+
+        assert_eq!(bytes.len(), position);
+        assert!(read_commutator(&bytes[..3], &mut 0).is_err());
+        assert!(read_commutator(&[0, 200, 0, 0], &mut 0).is_err());
+    }
+}
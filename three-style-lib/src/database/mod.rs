@@ -0,0 +1,112 @@
+//! Versioned binary storage of the best commutators of every case,
+//! for each piece type, moveset and maximum depth.
+
+mod builder;
+mod format;
+mod reader;
+
+pub use builder::DatabaseBuilder;
+pub use reader::Database;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commutator::{finder::CommutatorSearch, types::Cycle},
+        error::Error,
+        facelet::FaceletCube,
+        moves::MoveKind,
+        sticker::{Corner, Edge},
+    };
+
+    #[test]
+    fn test_database() {
+        let corner_moves = [MoveKind::R, MoveKind::U, MoveKind::D];
+        let edge_moves = [MoveKind::U, MoveKind::M];
+        let bytes = DatabaseBuilder::new(3)
+            .add::<Corner>(&corner_moves, 5)
+            .add::<Edge>(&edge_moves, 4)
+            .to_bytes();
+        let database = Database::from_bytes(bytes).unwrap();
+        let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+        let rotated = Cycle::new(Corner::RFD, Corner::UFR, Corner::UBL);
+        let commutators = database.get(cycle, &corner_moves, 5).unwrap().unwrap();
+        let expected = FaceletCube::try_from(cycle).unwrap();
+        let shortest = CommutatorSearch::new(cycle, &corner_moves, 5)
+            .find_all()
+            .iter()
+            .map(|c| c.expand().reduce().len())
+            .min();
+
+        assert!(!commutators.is_empty() && commutators.len() <= 3);
+        assert!(commutators
+            .iter()
+            .all(|c| FaceletCube::default().apply_alg(&c.expand()) == expected));
+        assert_eq!(shortest, Some(commutators[0].expand().reduce().len()));
+        assert_eq!(
+            Ok(Some(commutators.clone())),
+            database.get(rotated, &[MoveKind::D, MoveKind::U, MoveKind::R], 5)
+        );
+        assert!(database
+            .get(cycle, &corner_moves, 4)
+            .unwrap()
+            .unwrap()
+            .iter()
+            .all(|c| c.len() <= 4));
+
+        let edges = Cycle::new(Edge::UF, Edge::UB, Edge::DF);
+
+        assert!(database.get(edges, &edge_moves, 4).unwrap().is_some());
+        assert_eq!(Ok(None), database.get(cycle, &corner_moves, 6));
+        assert_eq!(
+            Ok(None),
+            database.get(cycle, &[MoveKind::R, MoveKind::U], 5)
+        );
+        assert_eq!(Ok(None), database.get(edges, &corner_moves, 4));
+    }
+
+    #[test]
+    fn test_invalid_database() {
+        let mut bytes = DatabaseBuilder::new(1)
+            .add::<Edge>(&[MoveKind::U, MoveKind::M], 4)
+            .to_bytes();
+
+        assert!(matches!(
+            Database::from_bytes(bytes[..20].to_vec()),
+            Err(Error::InvalidDatabase(_))
+        ));
+        assert!(matches!(
+            Database::from_bytes(b"not a database".to_vec()),
+            Err(Error::InvalidDatabase(_))
+        ));
+
+        bytes[8] = 2;
+
+        assert_eq!(
+            Error::UnsupportedDatabaseVersion(2),
+            Database::from_bytes(bytes).unwrap_err()
+        );
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mapped_database() {
+        let path = std::env::temp_dir().join(format!("three-style-test-{}.db", std::process::id()));
+        let allowed_moves = [MoveKind::U, MoveKind::M];
+        let cycle = Cycle::new(Edge::UF, Edge::UB, Edge::DF);
+
+        DatabaseBuilder::new(2)
+            .add::<Edge>(&allowed_moves, 4)
+            .write(&path)
+            .unwrap();
+
+        let mapped = Database::open_mmap(&path).unwrap();
+        let read = Database::open(&path).unwrap();
+
+        assert_eq!(
+            read.get(cycle, &allowed_moves, 4),
+            mapped.get(cycle, &allowed_moves, 4)
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+use super::format::{
+    case_bytes, invalid, moveset, read_commutator, read_u16, read_u32, PieceType, ENTRY_SIZE,
+    HEADER_SIZE, MAGIC, SECTION_SIZE, VERSION,
+};
+use crate::{
+    commutator::{
+        table::case_key,
+        types::{Commutator, Cycle},
+    },
+    error::Error,
+    moves::MoveKind,
+    sticker::Sticker,
+};
+use std::{cmp::Ordering, fs, ops::Deref, path::Path};
+
+#[derive(Debug)]
+enum Storage {
+    Owned(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Storage::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Storage::Mapped(map) => map,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SectionHeader {
+    piece: u8,
+    max_depth: u8,
+    moveset: u32,
+    case_count: usize,
+    index: usize,
+    data: usize,
+}
+
+/// Commutators stored by a [`super::DatabaseBuilder`], each case is found with a binary search.
+/// Only the headers are checked when loading, commutators are decoded when they are queried.
+#[derive(Debug)]
+pub struct Database {
+    storage: Storage,
+    sections: Vec<SectionHeader>,
+}
+
+impl Database {
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::new(Storage::Owned(bytes))
+    }
+
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|_| Error::ReadFile(path.display().to_string()))?;
+
+        Self::from_bytes(bytes)
+    }
+
+    /// Maps the file in memory instead of reading it, pages are loaded as cases are queried.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let error = || Error::ReadFile(path.display().to_string());
+        let file = fs::File::open(path).map_err(|_| error())?;
+        // SAFETY: the map is read only, the file is expected to not be modified while in use
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|_| error())?;
+
+        Self::new(Storage::Mapped(map))
+    }
+
+    fn new(storage: Storage) -> Result<Self, Error> {
+        let bytes = &storage[..];
+
+        if bytes.get(..MAGIC.len()) != Some(MAGIC) {
+            return Err(invalid("missing header"));
+        }
+
+        let version = read_u16(bytes, 8)?;
+
+        if version != VERSION {
+            return Err(Error::UnsupportedDatabaseVersion(version));
+        }
+
+        let count = read_u16(bytes, 10)? as usize;
+        let sections = (0..count)
+            .map(|i| read_section(bytes, HEADER_SIZE + i * SECTION_SIZE))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { storage, sections })
+    }
+
+    /// Returns the stored commutators of the cycle no longer than `max_depth`, the moveset must
+    /// be the same and the database must have been built with at least the same depth.
+    /// Returns `None` if no section matches or if no commutator fits.
+    pub fn get<T>(
+        &self,
+        cycle: Cycle<T>,
+        allowed_moves: &[MoveKind],
+        max_depth: u8,
+    ) -> Result<Option<Vec<Commutator>>, Error>
+    where
+        T: Sticker,
+    {
+        let piece = PieceType::of_size(T::SIZE) as u8;
+        let moveset = moveset(allowed_moves);
+        let section = self
+            .sections
+            .iter()
+            .filter(|s| s.piece == piece && s.moveset == moveset && s.max_depth >= max_depth)
+            .min_by_key(|s| s.max_depth);
+
+        let Some(section) = section else {
+            return Ok(None);
+        };
+
+        let bytes = &self.storage[..];
+        let key = case_bytes(case_key(cycle));
+        let index = &bytes[section.index..section.index + section.case_count * ENTRY_SIZE];
+
+        let Some(entry) = find_entry(index, &key) else {
+            return Ok(None);
+        };
+
+        let mut position = section.data + read_u32(entry, 4)? as usize;
+        let mut commutators = Vec::with_capacity(entry[3] as usize);
+
+        for _ in 0..entry[3] {
+            let commutator = read_commutator(bytes, &mut position)?;
+
+            if commutator.len() <= max_depth as usize {
+                commutators.push(commutator);
+            }
+        }
+
+        Ok((!commutators.is_empty()).then_some(commutators))
+    }
+}
+
+/// Binary searches the sorted index entries for the case key, in place.
+fn find_entry<'a>(index: &'a [u8], key: &[u8]) -> Option<&'a [u8]> {
+    let (mut low, mut high) = (0, index.len() / ENTRY_SIZE);
+
+    while low < high {
+        let middle = (low + high) / 2;
+        let entry = &index[middle * ENTRY_SIZE..(middle + 1) * ENTRY_SIZE];
+
+        match entry[..3].cmp(key) {
+            Ordering::Less => low = middle + 1,
+            Ordering::Greater => high = middle,
+            Ordering::Equal => return Some(entry),
+        }
+    }
+
+    None
+}
+
+fn read_section(bytes: &[u8], position: usize) -> Result<SectionHeader, Error> {
+    let header = bytes
+        .get(position..position + SECTION_SIZE)
+        .ok_or_else(|| invalid("unexpected end of file"))?;
+    let section = SectionHeader {
+        piece: header[0],
+        max_depth: header[1],
+        moveset: read_u32(header, 4)?,
+        case_count: read_u32(header, 8)? as usize,
+        index: read_u32(header, 12)? as usize,
+        data: read_u32(header, 16)? as usize,
+    };
+    let index_end = section.index + section.case_count * ENTRY_SIZE;
+
+    if index_end > section.data || section.data > bytes.len() {
+        return Err(invalid("section out of bounds"));
+    }
+
+    Ok(section)
+}
@@ -1,13 +1,49 @@
+use std::{fs, path::Path};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Clone, Error)]
 pub enum Error {
     #[error("Invalid move '{0}'")]
     InvalidMove(String),
+    #[error("Unexpected character in '{0}' at column {1}")]
+    UnexpectedCharacter(String, usize),
+    #[error("Invalid algorithm '{0}' at column {1}")]
+    InvalidAlg(String, usize),
     #[error("Invalid edge '{0}'")]
     InvalidEdgeString(String),
     #[error("Invalid corner '{0}'")]
     InvalidCornerString(String),
+    #[error("Invalid facelet '{0}'")]
+    InvalidFacelet(String),
     #[error("Invalid cycle '{0}'")]
     InvalidThreeCycle(String),
+    #[error("Invalid cube state '{0}'")]
+    InvalidCubeState(String),
+    #[error("Invalid twist '{0}'")]
+    InvalidTwist(String),
+    #[error("Invalid flip '{0}'")]
+    InvalidFlip(String),
+    #[error("Invalid parity '{0}'")]
+    InvalidParity(String),
+    #[error("Invalid letter '{0}'")]
+    InvalidLetter(char),
+    #[error("Invalid letter scheme '{0}'")]
+    InvalidLetterScheme(String),
+    #[error("Failed to read file '{0}'")]
+    ReadFile(String),
+    #[error("Invalid commutator '{0}' at column {1}")]
+    InvalidCommutator(String, usize),
+    #[error("Invalid weight '{0}' at line {1}")]
+    InvalidWeight(String, usize),
+    #[error("Failed to write file '{0}'")]
+    WriteFile(String),
+    #[error("Invalid commutator database ({0})")]
+    InvalidDatabase(String),
+    #[error("Unsupported database version {0}")]
+    UnsupportedDatabaseVersion(u16),
+}
+
+/// Reads a text file, failures are reported with the path of the file.
+pub(crate) fn read_to_string(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|_| Error::ReadFile(path.display().to_string()))
 }
@@ -1,6 +1,6 @@
 use super::{types::DEFAULT_STATE, Facelet, FaceletCube, FaceletState};
 use crate::moves::Move;
-use std::ops::Index;
+use std::{ops::Index, sync::OnceLock};
 
 /// State of the cube at the facelet level
 /// in the "is carried to" representation.
@@ -22,17 +22,30 @@ impl Default for FaceletPermutation {
     }
 }
 
-impl From<Move> for FaceletPermutation {
-    fn from(value: Move) -> Self {
-        let cube = FaceletCube::from(value);
-        let default = FaceletPermutation::default();
-        let mut res = FaceletPermutation::default();
+impl FaceletPermutation {
+    /// Returns the permutation of the given move from a table computed once for every move.
+    pub(crate) fn of_move(m: Move) -> &'static Self {
+        static MOVES: OnceLock<Vec<FaceletPermutation>> = OnceLock::new();
+
+        &MOVES.get_or_init(|| Move::all().map(compute_move).collect())[m.index()]
+    }
+}
 
-        for i in 0..54 {
-            res.0[cube[i] as usize] = default.0[i];
-        }
+fn compute_move(m: Move) -> FaceletPermutation {
+    let cube = FaceletCube::from(m);
+    let default = FaceletPermutation::default();
+    let mut res = FaceletPermutation::default();
 
-        res
+    for i in 0..54 {
+        res.0[cube[i] as usize] = default.0[i];
+    }
+
+    res
+}
+
+impl From<Move> for FaceletPermutation {
+    fn from(value: Move) -> Self {
+        FaceletPermutation::of_move(value).clone()
     }
 }
 
@@ -3,15 +3,18 @@ use super::{
     FaceletTarget,
 };
 use crate::{
-    commutator::types::{Commutator, Cycle, ThreeCycle},
+    commutator::types::{Commutator, Cycle, Flip, ThreeCycle, Twist},
     error::Error,
     moves::{Alg, Move, MoveCount, MoveKind},
+    parity::types::Parity,
+    sticker::Sticker,
 };
 use constants::*;
 use std::{
     collections::HashSet,
     fmt,
     ops::{Index, IndexMut, Mul},
+    sync::OnceLock,
 };
 
 /// State of the cube at the facelet level
@@ -41,7 +44,7 @@ impl FaceletCube {
     }
 
     pub fn apply_move(&self, m: Move) -> Self {
-        self * &FaceletCube::from(m)
+        self * FaceletCube::of_move(m)
     }
 
     pub fn apply_alg(&self, alg: &Alg) -> Self {
@@ -51,36 +54,118 @@ impl FaceletCube {
     pub fn apply_commutator(&self, commutator: &Commutator) -> Self {
         self.apply_alg(&commutator.expand())
     }
+
+    /// Twists two corners in place, see [`Twist`].
+    pub fn twist(self, twist: Twist) -> Result<Self, Error> {
+        self.orient([twist.first(), twist.second()])
+            .ok_or(Error::InvalidTwist(twist.to_string()))
+    }
+
+    /// Flips two edges in place, see [`Flip`].
+    pub fn flip(self, flip: Flip) -> Result<Self, Error> {
+        self.orient([flip.first(), flip.second()])
+            .ok_or(Error::InvalidFlip(flip.to_string()))
+    }
+
+    /// Swaps two corners and two edges, see [`Parity`].
+    pub fn swap(self, parity: Parity) -> Result<Self, Error> {
+        let [c1, c2] = parity.corners();
+        let [e1, e2] = parity.edges();
+
+        if c1.is_same_piece(&c2) || e1.is_same_piece(&e2) {
+            return Err(Error::InvalidParity(parity.to_string()));
+        }
+
+        let mut res = self.clone();
+        let pairs = [
+            (c1.to_facelets(), c2.to_facelets()),
+            (e1.to_facelets(), e2.to_facelets()),
+        ];
+
+        for (first, second) in pairs {
+            for (&a, &b) in first.iter().zip(second.iter()) {
+                res[a] = self[b];
+                res[b] = self[a];
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Carries the reference sticker of each target piece to the target position,
+    /// the orientations must cancel each other out to form a valid case.
+    fn orient<T>(self, targets: [T; 2]) -> Option<Self>
+    where
+        T: Sticker,
+    {
+        let [first, second] = targets;
+        let is_twisted = |t: T| t.orientation() != 0;
+        let total = first.orientation() + second.orientation();
+
+        if first.is_same_piece(&second)
+            || !is_twisted(first)
+            || !is_twisted(second)
+            || total % T::SIZE != 0
+        {
+            return None;
+        }
+
+        let mut res = self.clone();
+
+        for target in targets {
+            let facelets = target.reference().to_facelets();
+            let orientation = target.orientation();
+
+            for i in 0..T::SIZE {
+                res[facelets[(i + orientation) % T::SIZE]] = self[facelets[i]];
+            }
+        }
+
+        Some(res)
+    }
+}
+
+impl FaceletCube {
+    /// Returns the cube of the given move from a table computed once for every move.
+    pub(crate) fn of_move(m: Move) -> &'static Self {
+        static MOVES: OnceLock<Vec<FaceletCube>> = OnceLock::new();
+
+        &MOVES.get_or_init(|| Move::all().map(compute_move).collect())[m.index()]
+    }
+}
+
+fn compute_move(m: Move) -> FaceletCube {
+    let state = match m.kind {
+        MoveKind::U => U_CUBE,
+        MoveKind::F => F_CUBE,
+        MoveKind::R => R_CUBE,
+        MoveKind::B => B_CUBE,
+        MoveKind::L => L_CUBE,
+        MoveKind::D => D_CUBE,
+        MoveKind::M => M_CUBE,
+        MoveKind::S => S_CUBE,
+        MoveKind::E => E_CUBE,
+        MoveKind::X => X_CUBE,
+        MoveKind::Y => Y_CUBE,
+        MoveKind::Z => Z_CUBE,
+        MoveKind::Fw => FW_CUBE,
+        MoveKind::Lw => LW_CUBE,
+        MoveKind::Dw => DW_CUBE,
+        MoveKind::Uw => UW_CUBE,
+        MoveKind::Rw => RW_CUBE,
+        MoveKind::Bw => BW_CUBE,
+    };
+
+    match m.count {
+        MoveCount::Simple => state,
+        MoveCount::Double => state.mul(&state),
+        MoveCount::Prime => state.mul(&state).mul(&state),
+    }
 }
 
 impl From<Move> for FaceletCube {
     fn from(value: Move) -> Self {
-        let state = match value.kind {
-            MoveKind::U => U_CUBE,
-            MoveKind::F => F_CUBE,
-            MoveKind::R => R_CUBE,
-            MoveKind::B => B_CUBE,
-            MoveKind::L => L_CUBE,
-            MoveKind::D => D_CUBE,
-            MoveKind::M => M_CUBE,
-            MoveKind::S => S_CUBE,
-            MoveKind::E => E_CUBE,
-            MoveKind::X => X_CUBE,
-            MoveKind::Y => Y_CUBE,
-            MoveKind::Z => Z_CUBE,
-            MoveKind::Fw => FW_CUBE,
-            MoveKind::Lw => LW_CUBE,
-            MoveKind::Dw => DW_CUBE,
-            MoveKind::Uw => UW_CUBE,
-            MoveKind::Rw => RW_CUBE,
-            MoveKind::Bw => BW_CUBE,
-        };
-
-        match value.count {
-            MoveCount::Simple => state,
-            MoveCount::Double => state.mul(&state),
-            MoveCount::Prime => state.mul(&state).mul(&state),
-        }
+        FaceletCube::of_move(value).clone()
     }
 }
 
@@ -95,6 +180,30 @@ where
     }
 }
 
+impl TryFrom<Twist> for FaceletCube {
+    type Error = Error;
+
+    fn try_from(value: Twist) -> Result<Self, Self::Error> {
+        FaceletCube::default().twist(value)
+    }
+}
+
+impl TryFrom<Flip> for FaceletCube {
+    type Error = Error;
+
+    fn try_from(value: Flip) -> Result<Self, Self::Error> {
+        FaceletCube::default().flip(value)
+    }
+}
+
+impl TryFrom<Parity> for FaceletCube {
+    type Error = Error;
+
+    fn try_from(value: Parity) -> Result<Self, Self::Error> {
+        FaceletCube::default().swap(value)
+    }
+}
+
 impl Index<usize> for FaceletCube {
     type Output = F;
 
@@ -356,6 +465,65 @@ mod tests {
         sticker::{Corner, Edge},
     };
 
+    #[test]
+    fn test_corner_twist() {
+        let twist = Twist::new(Corner::FRU, Corner::BUR);
+        let cube = FaceletCube::try_from(twist).unwrap();
+        let scramble = alg!("[R' D' R D R' D' R D, U]");
+
+        assert_eq!(FaceletCube::default().apply_alg(&scramble), cube);
+        assert_eq!(FaceletCube::default(), cube.twist(twist.inverse()).unwrap());
+    }
+
+    #[test]
+    fn test_edge_flip() {
+        let flip = Flip::new(Edge::FU, Edge::BU);
+        let cube = FaceletCube::try_from(flip).unwrap();
+        let scramble = alg!("[(M' U)4, U]");
+
+        assert_eq!(FaceletCube::default().apply_alg(&scramble), cube);
+        assert_eq!(FaceletCube::default(), cube.flip(flip).unwrap());
+    }
+
+    #[test]
+    fn test_parity_swap() {
+        let parity = Parity::new([Corner::UFR, Corner::URB], [Edge::UL, Edge::UR]);
+        let cube = FaceletCube::try_from(parity).unwrap();
+        let scramble = alg!("R U R' U' R' F R2 U' R' U' R U R' F'");
+
+        assert_eq!(FaceletCube::default().apply_alg(&scramble), cube);
+        assert_eq!(FaceletCube::default(), cube.swap(parity).unwrap());
+        assert_eq!(
+            Err(Error::InvalidParity("UFR - FRU, UL - UR".to_owned())),
+            FaceletCube::try_from(Parity::new(
+                [Corner::UFR, Corner::FRU],
+                [Edge::UL, Edge::UR]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_orientation() {
+        let error = |s: &str| Err(Error::InvalidTwist(s.to_owned()));
+
+        assert_eq!(
+            error("FRU - RBU"),
+            FaceletCube::try_from(Twist::new(Corner::FRU, Corner::RBU))
+        );
+        assert_eq!(
+            error("UFR - BUR"),
+            FaceletCube::try_from(Twist::new(Corner::UFR, Corner::BUR))
+        );
+        assert_eq!(
+            error("FRU - RUF"),
+            FaceletCube::try_from(Twist::new(Corner::FRU, Corner::RUF))
+        );
+        assert_eq!(
+            Err(Error::InvalidFlip("UF - RU".to_owned())),
+            FaceletCube::try_from(Flip::new(Edge::UF, Edge::RU))
+        );
+    }
+
     #[test]
     fn test_primitive_moves() {
         let scramble = alg!("U R F D L B");
@@ -1,5 +1,6 @@
 use self::Facelet as F;
-use std::fmt;
+use crate::error::Error;
+use std::{fmt, str::FromStr};
 
 #[rustfmt::skip]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -31,6 +32,17 @@ impl fmt::Display for Facelet {
     }
 }
 
+impl FromStr for Facelet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DEFAULT_STATE
+            .into_iter()
+            .find(|f| f.to_string() == s)
+            .ok_or(Error::InvalidFacelet(s.to_owned()))
+    }
+}
+
 pub trait FaceletTarget {
     fn to_facelets(&self) -> Vec<Facelet>;
 
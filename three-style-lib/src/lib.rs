@@ -1,5 +1,13 @@
 pub mod commutator;
+pub mod cubie;
+pub mod database;
 pub mod error;
 pub mod facelet;
 pub mod moves;
+pub mod parity;
+pub mod sheet;
+pub mod solver;
 pub mod sticker;
+
+#[cfg(feature = "serde")]
+mod serialization;
@@ -1,6 +1,9 @@
 use crate::{
     error::Error,
-    moves::core::{Inverse, Move, MoveKind},
+    moves::{
+        core::{Inverse, Move, MoveKind},
+        Metric, Notation,
+    },
 };
 use std::{collections::BTreeMap, fmt, ops::Add, str::FromStr};
 
@@ -20,6 +23,21 @@ impl Alg {
         self.0.len()
     }
 
+    /// Returns the number of moves in the given metric,
+    /// rotations are only counted in the execution turn metric.
+    pub fn length(&self, metric: Metric) -> usize {
+        self.0
+            .iter()
+            .filter(|m| metric.counts_rotations() || !m.kind.is_rotation())
+            .map(|&m| metric.cost(m))
+            .sum()
+    }
+
+    /// Returns the number of moves in the given metric, rotations included.
+    pub fn length_with_rotations(&self, metric: Metric) -> usize {
+        self.0.iter().map(|&m| metric.cost(m)).sum()
+    }
+
     /// Returns `true` if the algorithm has no moves.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -103,13 +121,10 @@ impl IntoIterator for Alg {
 impl FromStr for Alg {
     type Err = Error;
 
+    /// Parses an algorithm written in the bracketed notation and evaluates it.
+    /// See [`Notation`] for the supported syntax.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let moves = s
-            .split_whitespace()
-            .map(Move::from_str)
-            .collect::<Result<Vec<_>, _>>();
-
-        moves.map(Self::new)
+        Notation::from_str(s).map(|n| n.evaluate())
     }
 }
 
@@ -190,6 +205,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bracketed_alg() {
+        let alg = alg!("[R2: [U, R' D R]]");
+        let expected = alg!("R2 U R' D R U' R' D' R R2");
+        assert_eq!(expected, alg);
+
+        let alg = alg!("(R U R' U')3");
+        let expected = alg!("R U R' U' R U R' U' R U R' U'");
+        assert_eq!(expected, alg);
+    }
+
+    #[test]
+    fn test_unspaced_alg() {
+        assert_eq!(alg!("R U R' U'"), alg!("RUR'U'"));
+        assert_eq!(alg!("Rw U2 R' M'"), alg!("RwU2R'2R"));
+        assert_eq!(alg!("R U'"), alg!("R4 R U3"));
+    }
+
     #[test]
     fn test_move_reduction() {
         let alg = alg!("U D2 D U'").reduce();
@@ -197,7 +230,7 @@ mod tests {
         assert_eq!(expected, alg);
 
         let alg = alg!("R U R' U D' U2").reduce();
-        let expected = alg!("R U R' U' D''");
+        let expected = alg!("R U R' U' D'");
         assert_eq!(expected, alg);
 
         let alg = alg!("U2 U2 D D' R").reduce();
@@ -207,5 +240,9 @@ mod tests {
         let alg = alg!("M R' U r R'").reduce();
         let expected = alg!("r' U M'");
         assert_eq!(expected, alg);
+
+        let alg = alg!("D E R E D2").reduce();
+        let expected = alg!("d R D2 E");
+        assert_eq!(expected, alg);
     }
 }
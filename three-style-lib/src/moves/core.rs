@@ -14,6 +14,13 @@ pub enum MoveKind {
 }
 
 impl MoveKind {
+    #[rustfmt::skip]
+    pub const ALL: [MoveKind; 18] = [
+        Self::U, Self::R, Self::F, Self::D, Self::L, Self::B,
+        Self::X, Self::Y, Self::Z, Self::M, Self::E, Self::S,
+        Self::Uw, Self::Rw, Self::Fw, Self::Dw, Self::Lw, Self::Bw,
+    ];
+
     pub fn is_side(self) -> bool {
         matches!(
             self,
@@ -44,15 +51,29 @@ impl MoveKind {
         ]
     }
 
-    pub fn parallel(&self) -> Vec<MoveKind> {
+    /// Returns the other move kinds turning around the same axis, rotations only list themselves.
+    pub fn parallel(&self) -> &'static [MoveKind] {
+        use MoveKind as M;
+
         match self {
-            MoveKind::E => vec![MoveKind::U, MoveKind::D],
-            MoveKind::M => vec![MoveKind::R, MoveKind::L],
-            MoveKind::S => vec![MoveKind::F, MoveKind::B],
-            MoveKind::U | MoveKind::D => vec![self.inverse(), MoveKind::E],
-            MoveKind::R | MoveKind::L => vec![self.inverse(), MoveKind::M],
-            MoveKind::F | MoveKind::B => vec![self.inverse(), MoveKind::S],
-            _ => vec![self.inverse()],
+            M::E => &[M::U, M::D],
+            M::M => &[M::R, M::L],
+            M::S => &[M::F, M::B],
+            M::U => &[M::D, M::E],
+            M::D => &[M::U, M::E],
+            M::R => &[M::L, M::M],
+            M::L => &[M::R, M::M],
+            M::F => &[M::B, M::S],
+            M::B => &[M::F, M::S],
+            M::Uw => &[M::Dw],
+            M::Dw => &[M::Uw],
+            M::Rw => &[M::Lw],
+            M::Lw => &[M::Rw],
+            M::Fw => &[M::Bw],
+            M::Bw => &[M::Fw],
+            M::X => &[M::X],
+            M::Y => &[M::Y],
+            M::Z => &[M::Z],
         }
     }
 }
@@ -100,6 +121,15 @@ impl FromStr for MoveKind {
             "b" => Ok(MoveKind::Bw),
             "l" => Ok(MoveKind::Lw),
             "d" => Ok(MoveKind::Dw),
+            "Uw" => Ok(MoveKind::Uw),
+            "Fw" => Ok(MoveKind::Fw),
+            "Rw" => Ok(MoveKind::Rw),
+            "Bw" => Ok(MoveKind::Bw),
+            "Lw" => Ok(MoveKind::Lw),
+            "Dw" => Ok(MoveKind::Dw),
+            "m" => Ok(MoveKind::M),
+            "s" => Ok(MoveKind::S),
+            "e" => Ok(MoveKind::E),
             _ => Err(Error::InvalidMove(s.to_owned())),
         }
     }
@@ -108,7 +138,10 @@ impl FromStr for MoveKind {
 impl fmt::Display for MoveKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = format!("{self:?}");
-        let s = if self.is_wide() { s.to_lowercase() } else { s };
+        let s = match self.is_wide() || self.is_rotation() {
+            true => s.to_lowercase(),
+            false => s,
+        };
         write!(f, "{}", &s[..1])
     }
 }
@@ -143,6 +176,18 @@ impl FromStr for MoveCount {
     }
 }
 
+impl MoveCount {
+    /// Normalizes a quarter turn amount modulo 4, returns `None` for null amounts.
+    pub fn from_amount(amount: i32) -> Option<Self> {
+        match amount.rem_euclid(4) {
+            1 => Some(MoveCount::Simple),
+            2 => Some(MoveCount::Double),
+            3 => Some(MoveCount::Prime),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for MoveCount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -160,24 +205,98 @@ pub struct Move {
 }
 
 impl Move {
+    /// Number of distinct moves, see [`Move::index`].
+    pub(crate) const COUNT: usize = MoveKind::ALL.len() * 3;
+
     pub fn new(kind: MoveKind, count: MoveCount) -> Self {
         Self { kind, count }
     }
 
+    /// Returns a unique index in `0..Move::COUNT`, used for move tables.
+    pub(crate) fn index(&self) -> usize {
+        self.kind as usize * 3 + self.count as usize - 1
+    }
+
+    /// Returns every move in the [`Move::index`] order.
+    pub(crate) fn all() -> impl Iterator<Item = Move> {
+        MoveKind::ALL.iter().flat_map(MoveKind::to_moves)
+    }
+
+    pub(crate) fn from_index(index: usize) -> Option<Self> {
+        Self::all().nth(index)
+    }
+
+    /// Reads a single move token at the start of `chars` and returns it along with
+    /// the number of consumed characters, the move is `None` if it amounts to nothing (e.g. `R4`).
+    /// Supports WCA and SiGN notations: `R`, `R2'`, `R3`, `Rw`, `r`, `2R`, `m`, `x`...
+    pub(crate) fn scan(chars: &[char]) -> Option<(Option<Self>, usize)> {
+        let mut position = 0;
+        let is_inner_slice = chars.first() == Some(&'2');
+
+        if is_inner_slice {
+            position += 1;
+        }
+
+        let face = *chars.get(position)?;
+        let is_wide = chars.get(position + 1) == Some(&'w') && face.is_ascii_uppercase();
+        let (kind, direction) = match (is_inner_slice, face) {
+            (true, 'R') => (MoveKind::M, -1),
+            (true, 'L') => (MoveKind::M, 1),
+            (true, 'U') => (MoveKind::E, -1),
+            (true, 'D') => (MoveKind::E, 1),
+            (true, 'F') => (MoveKind::S, 1),
+            (true, 'B') => (MoveKind::S, -1),
+            (true, _) => return None,
+            (false, _) if is_wide => (MoveKind::from_str(&format!("{face}w")).ok()?, 1),
+            (false, _) => (MoveKind::from_str(&face.to_string()).ok()?, 1),
+        };
+
+        position += if is_wide { 2 } else { 1 };
+
+        let digits_start = position;
+
+        while chars.get(position).is_some_and(char::is_ascii_digit) {
+            position += 1;
+        }
+
+        let amount = match digits_start == position {
+            true => 1,
+            false => {
+                chars[digits_start..position]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<i32>()
+                    .ok()?
+                    % 4
+            }
+        };
+        let is_prime = matches!(chars.get(position), Some('\'' | '’'));
+
+        if is_prime {
+            position += 1;
+        }
+
+        let amount = if is_prime { -amount } else { amount } * direction;
+        let m = MoveCount::from_amount(amount).map(|count| Move::new(kind, count));
+
+        Some((m, position))
+    }
+
     fn reduce(&self, rhs: Move) -> Option<Move> {
         use {MoveCount as C, MoveKind as M};
 
         let is_inversed = self.count.inverse() == rhs.count;
+        let is_same = self.count == rhs.count;
         let is_slice = self.kind.is_slice();
         let count = if is_slice { rhs.count } else { self.count }; // determined by wide or side moves
 
         match (self.kind, self.count, rhs.kind, rhs.count) {
             // wide move generators
-            (M::D, _, M::E, _) => Some(Move::new(M::Dw, count)),
+            (M::D, _, M::E, _) if is_same => Some(Move::new(M::Dw, count)),
             (M::E, _, M::U, _) if is_inversed => Some(Move::new(M::Uw, count)),
-            (M::L, _, M::M, _) => Some(Move::new(M::Lw, count)),
+            (M::L, _, M::M, _) if is_same => Some(Move::new(M::Lw, count)),
             (M::M, _, M::R, _) if is_inversed => Some(Move::new(M::Rw, count)),
-            (M::F, _, M::S, _) => Some(Move::new(M::Fw, count)),
+            (M::F, _, M::S, _) if is_same => Some(Move::new(M::Fw, count)),
             (M::S, _, M::B, _) if is_inversed => Some(Move::new(M::Bw, count)),
 
             // wide move reduction
@@ -195,11 +314,11 @@ impl Move {
             (M::B, C::Simple, M::Bw, C::Prime) => Some(Move::new(M::S, C::Simple)),
 
             (M::M, _, M::Lw, _) if is_inversed => Some(Move::new(M::L, count)),
-            (M::M, _, M::Rw, _) if self.count == rhs.count => Some(Move::new(M::R, count)),
+            (M::M, _, M::Rw, _) if is_same => Some(Move::new(M::R, count)),
             (M::E, _, M::Dw, _) if is_inversed => Some(Move::new(M::D, count)),
-            (M::E, _, M::Uw, _) if self.count == rhs.count => Some(Move::new(M::U, count)),
+            (M::E, _, M::Uw, _) if is_same => Some(Move::new(M::U, count)),
             (M::S, _, M::Fw, _) if is_inversed => Some(Move::new(M::F, count)),
-            (M::S, _, M::Bw, _) if self.count == rhs.count => Some(Move::new(M::B, count)),
+            (M::S, _, M::Bw, _) if is_same => Some(Move::new(M::B, count)),
 
             // move count reduction
             (_, _, _, _) if self.kind == rhs.kind && !is_inversed => {
@@ -234,12 +353,13 @@ impl FromStr for Move {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let kind = s.get(..1).unwrap_or_default();
-        let kind = MoveKind::from_str(kind)?;
-        let count = s.get(1..2).unwrap_or_default();
-        let count = MoveCount::from_str(count)?;
+        let chars = s.chars().collect::<Vec<_>>();
 
-        Ok(Self { kind, count })
+        match Move::scan(&chars) {
+            Some((Some(m), len)) if len == chars.len() => Ok(m),
+            Some((Some(_), len)) => Err(Error::UnexpectedCharacter(s.to_owned(), len + 1)),
+            _ => Err(Error::InvalidMove(s.to_owned())),
+        }
     }
 }
 
@@ -253,6 +373,12 @@ impl fmt::Display for Move {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_move_index() {
+        assert_eq!(Move::COUNT, Move::all().count());
+        assert!(Move::all().enumerate().all(|(i, m)| m.index() == i));
+    }
+
     #[test]
     fn test_move_str() {
         assert_eq!(
@@ -268,4 +394,60 @@ mod tests {
             Ok(Move::new(MoveKind::R, MoveCount::Prime))
         );
     }
+
+    #[test]
+    fn test_extended_move_str() {
+        let cases = [
+            ("R2'", Move::new(MoveKind::R, MoveCount::Double)),
+            ("R3", Move::new(MoveKind::R, MoveCount::Prime)),
+            ("R3'", Move::new(MoveKind::R, MoveCount::Simple)),
+            ("U5", Move::new(MoveKind::U, MoveCount::Simple)),
+            ("Rw", Move::new(MoveKind::Rw, MoveCount::Simple)),
+            ("Dw2", Move::new(MoveKind::Dw, MoveCount::Double)),
+            ("r'", Move::new(MoveKind::Rw, MoveCount::Prime)),
+            ("m2", Move::new(MoveKind::M, MoveCount::Double)),
+            ("2R", Move::new(MoveKind::M, MoveCount::Prime)),
+            ("2L'", Move::new(MoveKind::M, MoveCount::Prime)),
+            ("2U", Move::new(MoveKind::E, MoveCount::Prime)),
+            ("2F", Move::new(MoveKind::S, MoveCount::Simple)),
+            ("x'", Move::new(MoveKind::X, MoveCount::Prime)),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(Ok(expected), Move::from_str(input), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_move_string_round_trip() {
+        for kind in MoveKind::ALL {
+            let m = Move::new(kind, MoveCount::Prime);
+
+            assert_eq!(Ok(kind), MoveKind::from_str(&kind.to_string()));
+            assert_eq!(Ok(m), Move::from_str(&m.to_string()));
+        }
+
+        assert_eq!("y2", Move::new(MoveKind::Y, MoveCount::Double).to_string());
+    }
+
+    #[test]
+    fn test_invalid_move_str() {
+        assert_eq!(
+            Err(Error::UnexpectedCharacter("R'x".to_owned(), 3)),
+            Move::from_str("R'x")
+        );
+        assert_eq!(
+            Err(Error::UnexpectedCharacter("R2''".to_owned(), 4)),
+            Move::from_str("R2''")
+        );
+        assert_eq!(
+            Err(Error::InvalidMove("R4".to_owned())),
+            Move::from_str("R4")
+        );
+        assert_eq!(
+            Err(Error::InvalidMove("2M".to_owned())),
+            Move::from_str("2M")
+        );
+        assert_eq!(Err(Error::InvalidMove("".to_owned())), Move::from_str(""));
+    }
 }
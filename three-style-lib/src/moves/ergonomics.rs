@@ -0,0 +1,227 @@
+use super::{Alg, Move, MoveCount, MoveKind};
+use crate::error::{self, Error};
+use std::{collections::HashMap, path::Path, str::FromStr};
+
+/// Scores how comfortable an algorithm is to execute, lower is better.
+///
+/// Each move costs a base amount plus the penalties of its kind (`B`) and of the exact move (`D'`),
+/// half turns, rotations and regrips are penalized and algorithms using only moves
+/// of a friendly generator (`RUD`) get a bonus. When several generators match,
+/// only the largest bonus and the largest penalty are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErgonomicScorer {
+    move_cost: f64,
+    half_turn: f64,
+    rotation: f64,
+    regrip: f64,
+    kinds: HashMap<MoveKind, f64>,
+    moves: Vec<(Move, f64)>,
+    gens: Vec<(Vec<MoveKind>, f64)>,
+}
+
+impl ErgonomicScorer {
+    /// Loads the weights from a file, see the [`FromStr`] implementation for the format.
+    pub fn from_file<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let content = error::read_to_string(path.as_ref())?;
+
+        Self::from_str(&content)
+    }
+
+    pub fn score(&self, alg: &Alg) -> f64 {
+        let moves = alg.iter().map(|m| self.move_score(*m)).sum::<f64>();
+        let regrips = count_regrips(alg) as f64 * self.regrip;
+        let gens = self
+            .gens
+            .iter()
+            .filter(|(gen, _)| alg.iter().all(|m| gen.contains(&m.kind)))
+            .map(|(_, weight)| *weight);
+        let bonus = gens.clone().fold(0.0, f64::min);
+        let penalty = gens.fold(0.0, f64::max);
+
+        moves + regrips + bonus + penalty
+    }
+
+    fn move_score(&self, m: Move) -> f64 {
+        let base = match m.kind.is_rotation() {
+            true => self.rotation,
+            false => self.move_cost,
+        };
+        let half_turn = match m.count {
+            MoveCount::Double => self.half_turn,
+            _ => 0.0,
+        };
+        let kind = self.kinds.get(&m.kind).copied().unwrap_or_default();
+        let exact = self
+            .moves
+            .iter()
+            .filter(|(n, _)| *n == m)
+            .map(|(_, weight)| weight)
+            .sum::<f64>();
+
+        base + half_turn + kind + exact
+    }
+
+    fn set(&mut self, key: &str, value: f64) -> Option<()> {
+        match key {
+            "move" => self.move_cost = value,
+            "half_turn" => self.half_turn = value,
+            "rotation" => self.rotation = value,
+            "regrip" => self.regrip = value,
+            _ => match key.strip_prefix("gen.") {
+                Some(gen) => {
+                    let gen = gen
+                        .chars()
+                        .map(|c| MoveKind::from_str(&c.to_string()).ok())
+                        .collect::<Option<Vec<_>>>()?;
+
+                    self.gens.retain(|(g, _)| *g != gen);
+                    self.gens.push((gen, value));
+                }
+                None => match MoveKind::from_str(key) {
+                    Ok(kind) => {
+                        self.kinds.insert(kind, value);
+                    }
+                    Err(_) => {
+                        let m = Move::from_str(key).ok()?;
+
+                        self.moves.retain(|(n, _)| *n != m);
+                        self.moves.push((m, value));
+                    }
+                },
+            },
+        }
+
+        Some(())
+    }
+}
+
+/// Counts the regrips of the right hand, roughly estimated by tracking the wrist position:
+/// an outer layer turn done while the wrist is upside down (after `R2`) requires a regrip.
+fn count_regrips(alg: &Alg) -> usize {
+    let mut wrist = 0;
+    let mut regrips = 0;
+
+    for m in alg.iter() {
+        match m.kind {
+            MoveKind::R | MoveKind::Rw => wrist = (wrist + m.count as usize) % 4,
+            MoveKind::X | MoveKind::Y | MoveKind::Z => wrist = 0,
+            MoveKind::U | MoveKind::F | MoveKind::D | MoveKind::B if wrist == 2 => {
+                regrips += 1;
+                wrist = 0;
+            }
+            _ => {}
+        }
+    }
+
+    regrips
+}
+
+impl FromStr for ErgonomicScorer {
+    type Err = Error;
+
+    /// Parses `key = value` lines overriding the default weights, empty lines and `#` comments are ignored.
+    /// Keys are `move`, `half_turn`, `rotation`, `regrip`, a move kind (`B`), an exact move (`D'`)
+    /// or a friendly generator prefixed by `gen.` (`gen.RUD`), bonuses are negative weights.
+    /// Example: `B = 1.5`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scorer = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let error = || Error::InvalidWeight(line.to_owned(), index + 1);
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let value = value.trim().parse::<f64>().map_err(|_| error())?;
+
+            scorer.set(key.trim(), value).ok_or_else(error)?;
+        }
+
+        Ok(scorer)
+    }
+}
+
+impl Default for ErgonomicScorer {
+    fn default() -> Self {
+        let mut scorer = Self {
+            move_cost: 1.0,
+            half_turn: 0.2,
+            rotation: 2.0,
+            regrip: 1.5,
+            kinds: HashMap::new(),
+            moves: Vec::new(),
+            gens: Vec::new(),
+        };
+
+        let weights = [
+            ("B", 1.0),
+            ("L", 0.5),
+            ("D'", 0.3),
+            ("gen.RUD", -1.0),
+            ("gen.RUF", -1.0),
+        ];
+
+        for (key, value) in weights {
+            scorer.set(key, value);
+        }
+
+        scorer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg;
+
+    fn assert_close(expected: f64, value: f64) {
+        assert!((expected - value).abs() < 1e-9, "{expected} != {value}");
+    }
+
+    #[test]
+    fn test_default_weights() {
+        let scorer = ErgonomicScorer::default();
+        let score = |s: &str| scorer.score(&alg!(s));
+
+        assert_close(7.3, score("R' D' R U R' D R U'"));
+        assert!(score("R' D' R U R' D R U'") < score("R' B' R F R' B R F'"));
+        assert!(score("R U R' U'") < score("L U L' U'"));
+        assert_eq!(2, count_regrips(&alg!("R2 U R2 U'")));
+        assert_eq!(0, count_regrips(&alg!("R U R' U'")));
+    }
+
+    #[test]
+    fn test_weights_parsing() {
+        let input = "
+            # custom weights
+            move = 2
+            B = 0
+            D2 = 1.5 # half turn penalty included
+            gen.RU = -3
+        ";
+        let scorer = ErgonomicScorer::from_str(input).unwrap();
+
+        assert_close(2.0, scorer.score(&alg!("B")));
+        assert_close(2.7, scorer.score(&alg!("D2")));
+        assert_close(1.0, scorer.score(&alg!("R U")));
+
+        let scorer = ErgonomicScorer::from_str("gen.RU = 2\ngen.U = 0.5").unwrap();
+
+        assert_close(3.0, scorer.score(&alg!("R U")));
+        assert_close(2.2, scorer.score(&alg!("U2")));
+        assert_eq!(
+            Err(Error::InvalidWeight("X = 1".to_owned(), 2)),
+            ErgonomicScorer::from_str("move = 1\nX = 1")
+        );
+        assert_eq!(
+            Err(Error::InvalidWeight("B = heavy".to_owned(), 1)),
+            ErgonomicScorer::from_str("B = heavy")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use super::core::{Move, MoveCount};
+
+/// Move count metrics, see [`Alg::length`](super::Alg::length).
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Metric {
+    /// Half turn metric, slice moves count as two face turns.
+    Htm,
+    /// Quarter turn metric, half turns count as two quarter turns.
+    Qtm,
+    /// Slice turn metric, any layer turn counts as one move and rotations are free.
+    #[default]
+    Stm,
+    /// Execution turn metric, every token counts as one move, rotations included.
+    Etm,
+}
+
+impl Metric {
+    /// Returns `true` if rotations are counted even when lengths exclude them.
+    pub fn counts_rotations(self) -> bool {
+        self == Metric::Etm
+    }
+
+    /// Returns the cost of a single move, rotations are counted like outer layer turns.
+    pub fn cost(self, m: Move) -> usize {
+        let layers = if m.kind.is_slice() { 2 } else { 1 };
+        let quarters = match m.count {
+            MoveCount::Double => 2,
+            MoveCount::Simple | MoveCount::Prime => 1,
+        };
+
+        match self {
+            Metric::Htm => layers,
+            Metric::Qtm => layers * quarters,
+            Metric::Stm | Metric::Etm => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg;
+
+    #[test]
+    fn test_metrics() {
+        let alg = alg!("M2 U M U2 M' U M2");
+
+        assert_eq!(11, alg.length(Metric::Htm));
+        assert_eq!(16, alg.length(Metric::Qtm));
+        assert_eq!(7, alg.length(Metric::Stm));
+        assert_eq!(7, alg.length(Metric::Etm));
+
+        let alg = alg!("x R2 U r' y2");
+
+        assert_eq!(3, alg.length(Metric::Htm));
+        assert_eq!(4, alg.length(Metric::Qtm));
+        assert_eq!(3, alg.length(Metric::Stm));
+        assert_eq!(5, alg.length(Metric::Etm));
+        assert_eq!(5, alg.length_with_rotations(Metric::Htm));
+        assert_eq!(7, alg.length_with_rotations(Metric::Qtm));
+        assert_eq!(5, alg.length_with_rotations(Metric::Stm));
+        assert_eq!(5, alg.length_with_rotations(Metric::Etm));
+
+        let alg = alg!("y R U R' U' y'");
+
+        assert_eq!(4, alg.length(Metric::Stm));
+        assert_eq!(6, alg.length(Metric::Etm));
+    }
+}
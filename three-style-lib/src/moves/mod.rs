@@ -1,5 +1,13 @@
 mod alg;
 mod core;
+mod ergonomics;
+mod metric;
+mod notation;
 
 pub use self::core::{Inverse, Move, MoveCount, MoveKind};
 pub use alg::Alg;
+pub use ergonomics::ErgonomicScorer;
+pub use metric::Metric;
+pub use notation::Notation;
+
+pub(crate) use notation::parse_bracket;
@@ -0,0 +1,402 @@
+use crate::{
+    error::Error,
+    moves::{Alg, Inverse, Move},
+};
+use std::{fmt, str::FromStr};
+
+/// Highest repetition count of a group, larger counts are rejected by the parser.
+const MAX_REPETITIONS: usize = 99;
+
+/// Highest number of moves a group or a bracket can evaluate to, nested groups multiply
+/// their counts so the parser rejects larger nodes before they are evaluated.
+const MAX_LENGTH: usize = 10_000;
+
+/// Syntax tree of the bracketed algorithm notation.
+/// Example: `[R2: [U, R' D R]] (R U R' U')3`
+#[derive(Debug, PartialEq, Clone)]
+pub enum Notation {
+    /// A single move: `R`
+    Move(Move),
+    /// Space separated nodes: `R U R'`
+    Sequence(Vec<Notation>),
+    /// Parenthesized node repeated at most 99 times: `(R U R' U')3`
+    Group(Box<Notation>, usize),
+    /// `[A, B]` which evaluates to `A B A' B'`
+    Commutator(Box<Notation>, Box<Notation>),
+    /// `[A: B]` which evaluates to `A B A'`
+    Conjugate(Box<Notation>, Box<Notation>),
+}
+
+impl Notation {
+    /// Evaluates the notation down to a flat non-reduced algorithm.
+    pub fn evaluate(&self) -> Alg {
+        match self {
+            Notation::Move(m) => Alg::new([*m]),
+            Notation::Sequence(nodes) => nodes
+                .iter()
+                .fold(Alg::default(), |acc, n| acc + n.evaluate()),
+            Notation::Group(node, count) => {
+                let alg = node.evaluate();
+                (0..*count).fold(Alg::default(), |acc, _| acc + &alg)
+            }
+            Notation::Commutator(a, b) => {
+                let (a, b) = (a.evaluate(), b.evaluate());
+                &a + &b + a.inverse() + b.inverse()
+            }
+            Notation::Conjugate(a, b) => {
+                let a = a.evaluate();
+                &a + &b.evaluate() + a.inverse()
+            }
+        }
+    }
+}
+
+impl Notation {
+    /// Number of moves of the evaluated node, saturating instead of overflowing.
+    fn length(&self) -> usize {
+        match self {
+            Notation::Move(_) => 1,
+            Notation::Sequence(nodes) => nodes
+                .iter()
+                .fold(0, |acc, n| acc.saturating_add(n.length())),
+            Notation::Group(node, count) => node.length().saturating_mul(*count),
+            Notation::Commutator(a, b) => a.length().saturating_add(b.length()).saturating_mul(2),
+            Notation::Conjugate(a, b) => a.length().saturating_mul(2).saturating_add(b.length()),
+        }
+    }
+
+    /// Number of commutators and conjugates in the node.
+    pub(crate) fn bracket_count(&self) -> usize {
+        match self {
+            Notation::Move(_) => 0,
+            Notation::Sequence(nodes) => nodes.iter().map(Notation::bracket_count).sum(),
+            Notation::Group(node, _) => node.bracket_count(),
+            Notation::Commutator(a, b) | Notation::Conjugate(a, b) => {
+                1 + a.bracket_count() + b.bracket_count()
+            }
+        }
+    }
+}
+
+/// Parses a notation made of a single bracket, which the commutator notation is built on.
+/// Also returns the starting position of both operands of each bracket, in opening order.
+pub(crate) fn parse_bracket(s: &str) -> Result<(Notation, Vec<(usize, usize)>), Error> {
+    let mut parser = Parser::new(s);
+
+    parser.skip_whitespaces();
+
+    let node = parser.parse_bracket()?;
+
+    parser.skip_whitespaces();
+
+    match parser.position < parser.chars.len() {
+        true => Err(parser.error_at(parser.position)),
+        false => Ok((node, parser.operands)),
+    }
+}
+
+impl From<Alg> for Notation {
+    fn from(value: Alg) -> Self {
+        Notation::Sequence(value.into_iter().map(Notation::Move).collect())
+    }
+}
+
+impl FromStr for Notation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s).parse()
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Notation::Move(m) => write!(f, "{m}"),
+            Notation::Sequence(nodes) => {
+                let s = nodes
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                write!(f, "{s}")
+            }
+            Notation::Group(node, 1) => write!(f, "({node})"),
+            Notation::Group(node, count) => write!(f, "({node}){count}"),
+            Notation::Commutator(a, b) => write!(f, "[{a}, {b}]"),
+            Notation::Conjugate(a, b) => write!(f, "[{a}: {b}]"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Vec<char>,
+    position: usize,
+    operands: Vec<(usize, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.chars().collect(),
+            position: 0,
+            operands: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Notation, Error> {
+        let node = self.parse_sequence()?;
+
+        match self.position < self.chars.len() {
+            true => Err(self.error_at(self.position)),
+            false => Ok(node),
+        }
+    }
+
+    /// Parses nodes until a closing delimiter or the end of the input is reached.
+    /// Single node sequences are unwrapped.
+    fn parse_sequence(&mut self) -> Result<Notation, Error> {
+        let mut nodes = Vec::new();
+
+        loop {
+            self.skip_whitespaces();
+
+            match self.chars.get(self.position) {
+                Some('(') => nodes.push(self.parse_group()?),
+                Some('[') => nodes.push(self.parse_bracket()?),
+                Some(')' | ']' | ',' | ':') | None => break,
+                Some(_) => self.parse_moves(&mut nodes)?,
+            }
+        }
+
+        match nodes.len() {
+            1 => Ok(nodes.remove(0)),
+            _ => Ok(Notation::Sequence(nodes)),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<Notation, Error> {
+        self.expect('(')?;
+
+        let group_start = self.position - 1;
+        let start = self.position;
+        let node = self.parse_sequence()?;
+
+        if node == Notation::Sequence(Vec::new()) {
+            return Err(self.error_at(start));
+        }
+
+        self.expect(')')?;
+
+        let count_start = self.position;
+
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_ascii_digit())
+        {
+            self.position += 1;
+        }
+
+        let count = match count_start == self.position {
+            true => 1,
+            false => self.chars[count_start..self.position]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .ok()
+                .filter(|&count| count <= MAX_REPETITIONS)
+                .ok_or_else(|| self.error_at(count_start))?,
+        };
+
+        self.check_length(Notation::Group(Box::new(node), count), group_start)
+    }
+
+    fn parse_bracket(&mut self) -> Result<Notation, Error> {
+        self.expect('[')?;
+
+        let bracket_start = self.position - 1;
+
+        self.skip_whitespaces();
+
+        let bracket = self.operands.len();
+        let first_start = self.position;
+
+        self.operands.push((first_start, first_start));
+
+        let first = self.parse_sequence()?;
+        let separator = self.chars.get(self.position).copied();
+
+        if !matches!(separator, Some(',' | ':')) {
+            return Err(self.error_at(self.position));
+        }
+
+        self.position += 1;
+        self.skip_whitespaces();
+
+        let second_start = self.position;
+
+        self.operands[bracket].1 = second_start;
+
+        let second = self.parse_sequence()?;
+
+        self.expect(']')?;
+
+        let empty = Notation::Sequence(Vec::new());
+
+        if first == empty {
+            return Err(self.error_at(first_start));
+        }
+        if second == empty {
+            return Err(self.error_at(second_start));
+        }
+
+        let (first, second) = (Box::new(first), Box::new(second));
+
+        let node = match separator {
+            Some(',') => Notation::Commutator(first, second),
+            _ => Notation::Conjugate(first, second),
+        };
+
+        self.check_length(node, bracket_start)
+    }
+
+    /// Rejects a node starting at the position that would evaluate to too many moves.
+    fn check_length(&self, node: Notation, start: usize) -> Result<Notation, Error> {
+        match node.length() <= MAX_LENGTH {
+            true => Ok(node),
+            false => Err(self.error_at(start)),
+        }
+    }
+
+    /// Reads consecutive moves, spaces between moves are optional (e.g. `RUR'U'`).
+    fn parse_moves(&mut self, nodes: &mut Vec<Notation>) -> Result<(), Error> {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| !c.is_whitespace() && !matches!(c, '(' | ')' | '[' | ']' | ',' | ':'))
+        {
+            let (m, len) = Move::scan(&self.chars[self.position..])
+                .ok_or_else(|| self.error_at(self.position))?;
+
+            nodes.extend(m.map(Notation::Move));
+            self.position += len;
+        }
+
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespaces();
+
+        match self.chars.get(self.position) {
+            Some(&c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(self.error_at(self.position)),
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    fn error_at(&self, position: usize) -> Error {
+        Error::InvalidAlg(self.input.to_owned(), position + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alg;
+
+    #[test]
+    fn test_notation_parsing() {
+        let notation = Notation::from_str("[R2: [U, R' D R]]").unwrap();
+        let expected = Notation::Conjugate(
+            Box::new(Notation::Move(Move::from_str("R2").unwrap())),
+            Box::new(Notation::Commutator(
+                Box::new(Notation::Move(Move::from_str("U").unwrap())),
+                Box::new(Notation::from(alg!("R' D R"))),
+            )),
+        );
+
+        assert_eq!(expected, notation);
+
+        let notation = Notation::from_str("(R U R' U')3").unwrap();
+        let expected = Notation::Group(Box::new(Notation::from(alg!("R U R' U'"))), 3);
+
+        assert_eq!(expected, notation);
+    }
+
+    #[test]
+    fn test_notation_evaluation() {
+        let notation = Notation::from_str("[U, R' D R]").unwrap();
+        assert_eq!(alg!("U R' D R U' R' D' R"), notation.evaluate());
+
+        let notation = Notation::from_str("[R2: [U, R' D R]]").unwrap();
+        assert_eq!(alg!("R2 U R' D R U' R' D' R R2"), notation.evaluate());
+
+        let notation = Notation::from_str("(R U)2 [D: R] (U)").unwrap();
+        assert_eq!(alg!("R U R U D R D' U"), notation.evaluate());
+
+        let notation = Notation::from_str("[RUR', D2]").unwrap();
+        assert_eq!(alg!("R U R' D2 R U' R' D2"), notation.evaluate());
+    }
+
+    #[test]
+    fn test_notation_display() {
+        let inputs = [
+            "R U R' U'",
+            "[U, R' D R]",
+            "[R2: [U, R' D R]]",
+            "(R U R' U')3 D",
+            "[R: (U R U')2 D]",
+        ];
+
+        for input in inputs {
+            assert_eq!(input, Notation::from_str(input).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_invalid_notation() {
+        let error = |s: &str, column| Err(Error::InvalidAlg(s.to_owned(), column));
+
+        assert_eq!(error("[U, R", 6), Notation::from_str("[U, R"));
+        assert_eq!(error("[U R]", 5), Notation::from_str("[U R]"));
+        assert_eq!(error("(R U", 5), Notation::from_str("(R U"));
+        assert_eq!(error("()2", 2), Notation::from_str("()2"));
+        assert_eq!(error("R U X", 5), Notation::from_str("R U X"));
+        assert_eq!(error("RUR'X", 5), Notation::from_str("RUR'X"));
+        assert_eq!(error("R U]", 4), Notation::from_str("R U]"));
+        assert_eq!(error("[, U]", 2), Notation::from_str("[, U]"));
+        assert_eq!(error("(R)100", 4), Notation::from_str("(R)100"));
+        assert_eq!(
+            error("(R)99999999999", 4),
+            Notation::from_str("(R)99999999999")
+        );
+        assert!(Notation::from_str("(R)99").is_ok());
+        assert_eq!(
+            error("((((R)99)99)99)99", 2),
+            Notation::from_str("((((R)99)99)99)99")
+        );
+        assert_eq!(error("((R U)99)99", 1), Notation::from_str("((R U)99)99"));
+        assert!(Notation::from_str("((R U)50)50").is_ok());
+
+        let nested = "[[[[[[[[[[[[[R, U], U], U], U], U], U], U], U], U], U], U], U], U]";
+
+        assert_eq!(error(nested, 2), Notation::from_str(nested));
+    }
+}
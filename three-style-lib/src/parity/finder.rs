@@ -0,0 +1,128 @@
+use super::types::Parity;
+use crate::{
+    facelet::FaceletCube,
+    moves::{Alg, Inverse, Move, MoveKind},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Meet-in-the-middle search, the states reachable from the solved state are stored
+/// in a table and then looked up while searching from the case state.
+#[derive(Debug)]
+struct ParityFinder<'a> {
+    allowed_moves: &'a [Move],
+    table: HashMap<FaceletCube, Vec<Move>>,
+    current_moves: Vec<Move>,
+    results: Vec<Alg>,
+}
+
+impl<'a> ParityFinder<'a> {
+    fn new(allowed_moves: &'a [Move], table_depth: u8) -> Self {
+        let mut table = HashMap::from([(FaceletCube::default(), Vec::new())]);
+        let mut frontier = vec![(FaceletCube::default(), Vec::new())];
+
+        for _ in 0..table_depth {
+            let mut next_frontier = Vec::new();
+
+            for (state, moves) in frontier {
+                for &m in allowed_moves {
+                    if is_redundant(&moves, m) {
+                        continue;
+                    }
+
+                    let next_state = state.apply_move(m);
+
+                    if !table.contains_key(&next_state) {
+                        let mut next_moves = moves.clone();
+                        next_moves.push(m);
+                        table.insert(next_state.clone(), next_moves.clone());
+                        next_frontier.push((next_state, next_moves));
+                    }
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        Self {
+            allowed_moves,
+            table,
+            current_moves: Vec::new(),
+            results: Vec::new(),
+        }
+    }
+
+    fn search(mut self, state: FaceletCube, depth: u8) -> Vec<Alg> {
+        self.find_moves(state, depth);
+        self.results
+    }
+
+    fn find_moves(&mut self, state: FaceletCube, depth: u8) {
+        if let Some(moves) = self.table.get(&state) {
+            let first = Alg::new(self.current_moves.clone());
+            let second = Alg::new(moves.clone()).inverse();
+            self.results.push((first + second).reduce());
+        }
+
+        if depth == 0 {
+            return;
+        }
+
+        for &m in self.allowed_moves {
+            if is_redundant(&self.current_moves, m) {
+                continue;
+            }
+
+            self.current_moves.push(m);
+            self.find_moves(state.apply_move(m), depth - 1);
+            self.current_moves.pop();
+        }
+    }
+}
+
+fn is_redundant(moves: &[Move], m: Move) -> bool {
+    moves.last().is_some_and(|last| last.kind == m.kind)
+}
+
+/// Returns algorithms performing the given parity case using only the allowed moves,
+/// sorted by length.
+pub fn find_parity_algs(parity: Parity, allowed_moves: &[MoveKind], max_depth: u8) -> Vec<Alg> {
+    let initial_state = FaceletCube::try_from(parity);
+
+    if let Ok(state) = initial_state {
+        let allowed_moves = allowed_moves
+            .iter()
+            .flat_map(MoveKind::to_moves)
+            .collect::<Vec<_>>();
+        let table_depth = max_depth / 2;
+        let finder = ParityFinder::new(&allowed_moves, table_depth);
+        let mut results = finder.search(state, max_depth - table_depth);
+
+        let mut seen = HashSet::new();
+
+        results.sort_by_key(Alg::len);
+        results.retain(|alg| seen.insert(alg.to_string()));
+        results
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sticker::{Corner, Edge};
+
+    #[test]
+    fn test_parity_algs() {
+        let parity = Parity::new([Corner::UFR, Corner::URB], [Edge::UL, Edge::UR]);
+        let initial_state = FaceletCube::try_from(parity).unwrap();
+        let allowed_moves = vec![MoveKind::R, MoveKind::U, MoveKind::F];
+        let results = find_parity_algs(parity, &allowed_moves, 14);
+
+        assert!(!results.is_empty());
+        assert!(results.windows(2).all(|w| w[0].len() <= w[1].len()));
+        assert!(results
+            .into_iter()
+            .all(|alg| initial_state.apply_alg(&alg) == FaceletCube::default()));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod finder;
+pub mod types;
@@ -0,0 +1,33 @@
+use crate::sticker::{Corner, Edge};
+use std::fmt;
+
+/// Corner swap combined with an edge swap, the targets of each pair exchange places.
+/// Example: `UFR - URB, UL - UR` is the case solved by the T permutation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parity {
+    corners: [Corner; 2],
+    edges: [Edge; 2],
+}
+
+impl Parity {
+    pub fn new(corners: [Corner; 2], edges: [Edge; 2]) -> Self {
+        Self { corners, edges }
+    }
+
+    pub fn corners(&self) -> [Corner; 2] {
+        self.corners
+    }
+
+    pub fn edges(&self) -> [Edge; 2] {
+        self.edges
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [c1, c2] = self.corners;
+        let [e1, e2] = self.edges;
+
+        write!(f, "{c1} - {c2}, {e1} - {e2}")
+    }
+}
@@ -0,0 +1,164 @@
+//! Serde support, moves, algorithms and commutators are represented by their notation
+//! while stickers and facelets are represented by their names.
+
+use crate::{
+    commutator::types::{Commutator, Cycle},
+    facelet::{Facelet, FaceletCube},
+    moves::{Alg, Move, MoveKind},
+    sticker::{Corner, Edge},
+};
+use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+
+/// Implements serialization using `Display` and deserialization using `FromStr`.
+macro_rules! impl_string_serde {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    String::deserialize(deserializer)?
+                        .parse()
+                        .map_err(de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_string_serde!(Move, MoveKind, Alg, Commutator, Corner, Edge, Facelet);
+
+impl<T> Serialize for Cycle<T>
+where
+    T: Clone + Copy + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(3)?;
+
+        tuple.serialize_element(&self.first())?;
+        tuple.serialize_element(&self.second())?;
+        tuple.serialize_element(&self.third())?;
+        tuple.end()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Cycle<T>
+where
+    T: Clone + Copy + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [first, second, third] = <[T; 3]>::deserialize(deserializer)?;
+
+        Ok(Cycle::new(first, second, third))
+    }
+}
+
+impl Serialize for FaceletCube {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq((0..54).map(|i| self[i]))
+    }
+}
+
+impl<'de> Deserialize<'de> for FaceletCube {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let facelets = Vec::<Facelet>::deserialize(deserializer)?;
+        let is_permutation = facelets.iter().collect::<HashSet<_>>().len() == facelets.len();
+        let state = facelets
+            .try_into()
+            .map_err(|v: Vec<_>| de::Error::invalid_length(v.len(), &"54 facelets"))?;
+
+        match is_permutation {
+            true => Ok(FaceletCube::new(state)),
+            false => Err(de::Error::custom("duplicate facelets in the cube state")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alg, moves::MoveCount};
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+
+    fn assert_round_trip<T>(value: T, expected_json: &str)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let json = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(expected_json, json);
+        assert_eq!(value, serde_json::from_str::<T>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let commutator = "[D: [R' D' R, U2]]".parse::<Commutator>().unwrap();
+        let rotated = "[x': [R' D' R, U2]]".parse::<Commutator>().unwrap();
+
+        assert_round_trip(Move::new(MoveKind::R, MoveCount::Prime), "\"R'\"");
+        assert_round_trip(MoveKind::Rw, "\"r\"");
+        assert_round_trip(MoveKind::X, "\"x\"");
+        assert_round_trip(alg!("x' R U R' U' y2"), "\"x' R U R' U' y2\"");
+        assert_round_trip(alg!("R U R' U'"), "\"R U R' U'\"");
+        assert_round_trip(commutator, "\"[D: [R' D' R, U2]]\"");
+        assert_round_trip(rotated, "\"[x': [R' D' R, U2]]\"");
+        assert_round_trip(Corner::UFR, "\"UFR\"");
+        assert_round_trip(Edge::BU, "\"BU\"");
+        assert_round_trip(Facelet::R4, "\"R4\"");
+    }
+
+    #[test]
+    fn test_cycle_round_trip() {
+        let cycle = Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD);
+        let json = serde_json::to_string(&cycle).unwrap();
+
+        assert_eq!("[\"UFR\",\"UBL\",\"RFD\"]", json);
+        assert_eq!(cycle, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_cube_round_trip() {
+        let cube = FaceletCube::default().apply_alg(&alg!("R U R' U'"));
+        let json = serde_json::to_string(&cube).unwrap();
+
+        let solved = serde_json::to_string(&FaceletCube::default()).unwrap();
+
+        assert_eq!(cube, serde_json::from_str(&json).unwrap());
+        assert!(solved.starts_with("[\"U0\",\"U1\","));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(serde_json::from_str::<Move>("\"X\"").is_err());
+        assert!(serde_json::from_str::<Corner>("\"UF\"").is_err());
+        assert!(serde_json::from_str::<FaceletCube>("[\"U0\", \"U1\"]").is_err());
+
+        let duplicated = serde_json::to_string(&vec![Facelet::U0; 54]).unwrap();
+
+        assert!(serde_json::from_str::<FaceletCube>(&duplicated).is_err());
+    }
+}
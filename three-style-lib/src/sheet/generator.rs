@@ -0,0 +1,135 @@
+use super::types::{Sheet, SheetEntry};
+use crate::{
+    commutator::{
+        finder::{best_commutator, find_corner_commutators, find_edge_commutators},
+        table::CaseTable,
+        types::{Commutator, Cycle},
+    },
+    moves::MoveKind,
+    sticker::{Corner, Edge, Sticker},
+};
+
+/// Returns every valid 3-cycle starting from the buffer,
+/// the targets can't be on the buffer piece or on the same piece as each other.
+pub fn sheet_cycles<T>(buffer: T) -> Vec<Cycle<T>>
+where
+    T: Sticker,
+{
+    let targets = T::ALL
+        .into_iter()
+        .filter(|t| !t.is_same_piece(&buffer))
+        .collect::<Vec<_>>();
+
+    targets
+        .iter()
+        .flat_map(|&first| {
+            targets
+                .iter()
+                .filter(move |second| !second.is_same_piece(&first))
+                .map(move |&second| Cycle::new(buffer, first, second))
+        })
+        .collect()
+}
+
+fn generate_sheet<T, F>(buffer: T, find: F) -> Sheet<T>
+where
+    T: Sticker,
+    F: Fn(Cycle<T>) -> Vec<Commutator>,
+{
+    let entries = sheet_cycles(buffer)
+        .into_iter()
+        .map(|cycle| SheetEntry {
+            cycle,
+            commutator: best_commutator(find(cycle)),
+        })
+        .collect();
+
+    Sheet { buffer, entries }
+}
+
+/// Finds the shortest commutator for every corner target pair of the buffer (378 cases).
+pub fn generate_corner_sheet(
+    buffer: Corner,
+    allowed_moves: &[MoveKind],
+    max_depth: u8,
+    threads: usize,
+) -> Sheet<Corner> {
+    generate_sheet(buffer, |cycle| {
+        find_corner_commutators(cycle, allowed_moves, max_depth, threads)
+    })
+}
+
+/// Finds the shortest commutator for every edge target pair of the buffer (440 cases).
+pub fn generate_edge_sheet(
+    buffer: Edge,
+    allowed_moves: &[MoveKind],
+    max_depth: u8,
+    threads: usize,
+) -> Sheet<Edge> {
+    generate_sheet(buffer, |cycle| {
+        find_edge_commutators(cycle, allowed_moves, max_depth, threads)
+    })
+}
+
+/// Answers every target pair of the buffer with a lookup in a precomputed table.
+pub fn generate_sheet_from_table<T>(buffer: T, table: &CaseTable<T>) -> Sheet<T>
+where
+    T: Sticker,
+{
+    generate_sheet(buffer, |cycle| {
+        table.get(cycle).cloned().into_iter().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facelet::FaceletCube;
+
+    #[test]
+    fn test_sheet_cycles() {
+        let corners = sheet_cycles(Corner::UFR);
+        let edges = sheet_cycles(Edge::UF);
+
+        assert_eq!(378, corners.len());
+        assert_eq!(440, edges.len());
+        assert!(corners.iter().all(|c| FaceletCube::try_from(*c).is_ok()));
+        assert!(edges.iter().all(|c| FaceletCube::try_from(*c).is_ok()));
+        assert!(!corners.contains(&Cycle::new(Corner::UFR, Corner::RUF, Corner::UBL)));
+        assert!(!edges.contains(&Cycle::new(Edge::UF, Edge::UB, Edge::BU)));
+    }
+
+    #[test]
+    fn test_corner_sheet() {
+        let allowed_moves = [MoveKind::R, MoveKind::U, MoveKind::D];
+        let sheet = generate_corner_sheet(Corner::UFR, &allowed_moves, 4, 1);
+        let entry = sheet.get(Corner::UBL, Corner::RFD).unwrap();
+        let commutator = entry.commutator.as_ref().unwrap();
+        let expected = FaceletCube::try_from(entry.cycle).unwrap();
+
+        assert_eq!(378, sheet.entries.len());
+        assert_eq!(
+            expected,
+            FaceletCube::default().apply_alg(&commutator.expand())
+        );
+        assert!(sheet.missing().count() > 0);
+    }
+
+    #[test]
+    fn test_table_sheet() {
+        let allowed_moves = [MoveKind::R, MoveKind::U, MoveKind::D];
+        let table = CaseTable::build(&allowed_moves, 6);
+        let sheet = generate_sheet_from_table(Corner::UFR, &table);
+        let expected = generate_corner_sheet(Corner::UFR, &allowed_moves, 6, 1);
+        let missing = |sheet: &Sheet<Corner>| sheet.missing().map(|e| e.cycle).collect::<Vec<_>>();
+
+        assert_eq!(378, sheet.entries.len());
+        assert!(missing(&sheet)
+            .iter()
+            .all(|c| missing(&expected).contains(c)));
+        assert!(sheet
+            .entries
+            .iter()
+            .all(|e| e.commutator.as_ref() == table.get(e.cycle)));
+    }
+}
@@ -0,0 +1,104 @@
+use super::types::Sheet;
+use crate::sticker::Sticker;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridFormat {
+    Csv,
+    Tsv,
+}
+
+impl GridFormat {
+    fn separator(&self) -> char {
+        match self {
+            GridFormat::Csv => ',',
+            GridFormat::Tsv => '\t',
+        }
+    }
+
+    /// Quotes CSV cells containing separators or quotes, TSV cells are written as is.
+    fn escape(&self, cell: &str) -> String {
+        match self {
+            GridFormat::Csv if cell.contains([',', '"', '\n']) => {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            }
+            _ => cell.to_owned(),
+        }
+    }
+}
+
+/// Writes the sheet as a grid with the first targets as rows and the second targets as columns,
+/// targets follow the sticker order and are labeled using the given function.
+/// Cells of invalid pairs or with no commutator found are left empty.
+pub fn format_grid<T, F>(sheet: &Sheet<T>, format: GridFormat, label: F) -> String
+where
+    T: Sticker,
+    F: Fn(T) -> String,
+{
+    let targets = T::ALL
+        .into_iter()
+        .filter(|t| !t.is_same_piece(&sheet.buffer))
+        .collect::<Vec<_>>();
+    let write_row = |cells: Vec<String>| {
+        let cells = cells.iter().map(|c| format.escape(c)).collect::<Vec<_>>();
+        let separator = format.separator().to_string();
+        cells.join(&separator)
+    };
+    let header = std::iter::once(String::new())
+        .chain(targets.iter().map(|&t| label(t)))
+        .collect();
+    let mut rows = vec![write_row(header)];
+
+    for &first in &targets {
+        let cells = std::iter::once(label(first))
+            .chain(targets.iter().map(|&second| {
+                sheet
+                    .get(first, second)
+                    .and_then(|e| e.commutator.as_ref())
+                    .map(|c| c.to_string())
+                    .unwrap_or_default()
+            }))
+            .collect();
+
+        rows.push(write_row(cells));
+    }
+
+    rows.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commutator::types::Cycle,
+        sheet::types::SheetEntry,
+        sticker::{Corner, LetterScheme},
+    };
+
+    #[test]
+    fn test_grid_format() {
+        let commutator = "[R' D' R, U]".parse().unwrap();
+        let sheet = Sheet {
+            buffer: Corner::UFR,
+            entries: vec![SheetEntry {
+                cycle: Cycle::new(Corner::UFR, Corner::UBL, Corner::RFD),
+                commutator: Some(commutator),
+            }],
+        };
+        let scheme = LetterScheme::speffz();
+        let csv = format_grid(&sheet, GridFormat::Csv, |c| {
+            scheme.corner_letter(c).to_string()
+        });
+        let tsv = format_grid(&sheet, GridFormat::Tsv, |c| c.to_string());
+        let csv_rows = csv.lines().collect::<Vec<_>>();
+        let tsv_rows = tsv.lines().collect::<Vec<_>>();
+
+        assert_eq!(22, csv_rows.len());
+        assert!(csv_rows[0].starts_with(",A,R,E,B,N,Q,D,F,I,"));
+        assert!(csv_rows[1].starts_with("A,,,,,,"));
+        assert!(csv_rows[2].starts_with("R,,,,,,"));
+        assert!(csv_rows[1].ends_with(",\"[R' D' R, U]\",,,,,,,"));
+        assert!(tsv_rows[0].starts_with("\tUBL\tBLU\tLUB\tURB\t"));
+        assert_eq!(22, tsv_rows[1].split('\t').count());
+        assert_eq!(Some("[R' D' R, U]"), tsv_rows[1].split('\t').nth(14));
+    }
+}
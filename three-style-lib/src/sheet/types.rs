@@ -0,0 +1,35 @@
+use crate::{
+    commutator::types::{Commutator, Cycle},
+    sticker::Sticker,
+};
+
+/// Buffer 3-cycle along with the best commutator found for it, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetEntry<T> {
+    pub cycle: Cycle<T>,
+    pub commutator: Option<Commutator>,
+}
+
+/// Commutators for every target pair of a buffer,
+/// entries are ordered by the first target and then by the second target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet<T> {
+    pub buffer: T,
+    pub entries: Vec<SheetEntry<T>>,
+}
+
+impl<T> Sheet<T>
+where
+    T: Sticker,
+{
+    pub fn get(&self, first: T, second: T) -> Option<&SheetEntry<T>> {
+        self.entries
+            .iter()
+            .find(|e| e.cycle.second() == first && e.cycle.third() == second)
+    }
+
+    /// Returns the entries with no commutator found within the constraints.
+    pub fn missing(&self) -> impl Iterator<Item = &SheetEntry<T>> {
+        self.entries.iter().filter(|e| e.commutator.is_none())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{facelet::FaceletCube, sticker::Sticker};
+
+/// Result of tracing the pieces of one type from a buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Memo<T> {
+    /// Visited stickers in order, including cycle breaks.
+    pub targets: Vec<T>,
+    /// Pieces oriented in place, given as the sticker holding their reference sticker.
+    pub misoriented: Vec<T>,
+}
+
+/// Traces the pieces of type `T` from the buffer by successively
+/// swapping the buffer with the target it currently holds.
+pub fn trace<T>(state: &FaceletCube, buffer: T) -> Memo<T>
+where
+    T: Sticker,
+{
+    let mut state = state.clone();
+    let mut targets = Vec::new();
+
+    loop {
+        let target = T::from_facelet(state[buffer.as_facelet()]).unwrap();
+        let target = match target.is_same_piece(&buffer) {
+            false => target,
+            true => match find_cycle_break(&state, buffer) {
+                Some(target) => target,
+                None => break,
+            },
+        };
+
+        targets.push(target);
+        swap_pieces(&mut state, buffer, target);
+    }
+
+    let misoriented = T::ALL
+        .into_iter()
+        .filter(|s| s.orientation() == 0)
+        .filter_map(|r| {
+            r.piece_stickers()
+                .into_iter()
+                .find(|s| state[s.as_facelet()] == r.as_facelet())
+                .filter(|&s| s != r)
+        })
+        .collect();
+
+    Memo {
+        targets,
+        misoriented,
+    }
+}
+
+/// Swaps the content of the two pieces, the stickers of each piece are aligned
+/// starting from the given ones.
+pub fn swap_pieces<T>(state: &mut FaceletCube, first: T, second: T)
+where
+    T: Sticker,
+{
+    let first = first.to_facelets();
+    let second = second.to_facelets();
+    let current = state.clone();
+
+    for (&a, &b) in first.iter().zip(second.iter()) {
+        state[a] = current[b];
+        state[b] = current[a];
+    }
+}
+
+/// Returns the reference sticker of the first piece that is not in place.
+fn find_cycle_break<T>(state: &FaceletCube, buffer: T) -> Option<T>
+where
+    T: Sticker,
+{
+    T::ALL.into_iter().find(|s| {
+        let current = T::from_facelet(state[s.as_facelet()]).unwrap();
+        s.orientation() == 0 && !s.is_same_piece(&buffer) && !s.is_same_piece(&current)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alg,
+        sticker::{Corner, Edge},
+    };
+
+    #[test]
+    fn test_corner_trace() {
+        let state = FaceletCube::default().apply_alg(&alg!("[U, R' D' R]"));
+        let memo = trace(&state, Corner::UFR);
+
+        assert_eq!(vec![Corner::URB, Corner::RFD], memo.targets);
+        assert!(memo.misoriented.is_empty());
+    }
+
+    #[test]
+    fn test_cycle_break() {
+        let state = FaceletCube::default().apply_alg(&alg!("[M2, U]"));
+        let memo = trace(&state, Edge::UF);
+        let expected = vec![Edge::DB, Edge::UR, Edge::UB, Edge::DF, Edge::UL, Edge::UB];
+
+        assert_eq!(expected, memo.targets);
+        assert!(memo.misoriented.is_empty());
+    }
+
+    #[test]
+    fn test_misoriented_pieces() {
+        let state = FaceletCube::default().apply_alg(&alg!("[R' D' R D R' D' R D, U]"));
+        let memo = trace(&state, Corner::UFR);
+
+        assert!(memo.targets.is_empty());
+        assert_eq!(2, memo.misoriented.len());
+    }
+}
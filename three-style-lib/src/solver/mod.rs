@@ -0,0 +1,3 @@
+pub mod memo;
+pub mod pipeline;
+pub mod types;
@@ -0,0 +1,211 @@
+use super::{
+    memo::{swap_pieces, trace},
+    types::{Solution, SolverOptions, Step, TargetPair},
+};
+use crate::{
+    commutator::{
+        finder::{
+            best_commutator, find_corner_commutators, find_corner_twists, find_edge_commutators,
+            find_edge_flips,
+        },
+        types::{Cycle, Flip, Twist},
+    },
+    error::Error,
+    facelet::FaceletCube,
+    moves::Alg,
+    parity::{finder::find_parity_algs, types::Parity},
+    sticker::{Corner, Edge, Sticker},
+};
+use std::collections::VecDeque;
+
+/// Traces the scramble from the buffers and finds an algorithm for every step.
+/// Steps with no algorithm found within the constraints are still returned,
+/// targets are named with the given sticker to letter mappings.
+pub fn solve<C, E>(
+    scramble: &Alg,
+    options: &SolverOptions,
+    corner_letter: C,
+    edge_letter: E,
+) -> Result<Solution, Error>
+where
+    C: Fn(Corner) -> char,
+    E: Fn(Edge) -> char,
+{
+    let SolverOptions {
+        corner_buffer,
+        edge_buffer,
+        parity_edge,
+        ..
+    } = *options;
+
+    if edge_buffer.is_same_piece(&parity_edge) {
+        let parity = Parity::new([corner_buffer; 2], [edge_buffer, parity_edge]);
+        return Err(Error::InvalidParity(parity.to_string()));
+    }
+
+    let mut state = FaceletCube::default().apply_alg(scramble);
+    let mut steps = Vec::new();
+    let corners = trace(&state, corner_buffer);
+
+    for targets in corners.targets.chunks(2) {
+        match *targets {
+            [first, second] => {
+                let cycle = Cycle::new(corner_buffer, first, second);
+                let commutator = best_commutator(find_corner_commutators(
+                    cycle,
+                    &options.allowed_moves,
+                    options.max_depth,
+                    options.threads,
+                ));
+
+                steps.push(Step::Corners(TargetPair {
+                    cycle,
+                    letters: [first, second].map(&corner_letter),
+                    commutator,
+                }));
+            }
+            [last] => {
+                let parity = Parity::new([corner_buffer, last], [edge_buffer, parity_edge]);
+                let alg = find_parity_algs(parity, &options.parity_moves, options.parity_depth)
+                    .into_iter()
+                    .next();
+
+                swap_pieces(&mut state, edge_buffer, parity_edge);
+                steps.push(Step::Parity {
+                    parity,
+                    letter: corner_letter(last),
+                    alg,
+                });
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    for [first, second] in pair_misoriented(corners.misoriented) {
+        let twist = Twist::new(first, second);
+        let alg = find_corner_twists(twist, &options.allowed_moves, options.max_depth)
+            .into_iter()
+            .next();
+
+        steps.push(Step::Twist { twist, alg });
+    }
+
+    let edges = trace(&state, edge_buffer);
+
+    for targets in edges.targets.chunks(2) {
+        if let [first, second] = *targets {
+            let cycle = Cycle::new(edge_buffer, first, second);
+            let commutator = best_commutator(find_edge_commutators(
+                cycle,
+                &options.allowed_moves,
+                options.max_depth,
+                options.threads,
+            ));
+
+            steps.push(Step::Edges(TargetPair {
+                cycle,
+                letters: [first, second].map(&edge_letter),
+                commutator,
+            }));
+        }
+    }
+
+    for [first, second] in pair_misoriented(edges.misoriented) {
+        let flip = Flip::new(first, second);
+        let alg = find_edge_flips(flip, &options.allowed_moves, options.max_depth)
+            .into_iter()
+            .next();
+
+        steps.push(Step::Flip { flip, alg });
+    }
+
+    Ok(Solution { steps })
+}
+
+/// Pairs misoriented pieces into valid twists or flips performing their solution.
+/// Pieces twisted in the same direction are solved one by one,
+/// the compensating twist being carried to the next piece.
+fn pair_misoriented<T>(pieces: Vec<T>) -> Vec<[T; 2]>
+where
+    T: Sticker,
+{
+    let solving = |t: T| t.reference().rotate(T::SIZE - t.orientation());
+    let mut pieces = VecDeque::from(pieces);
+    let mut pairs = Vec::new();
+
+    while let Some(first) = pieces.pop_front() {
+        let opposite = pieces
+            .iter()
+            .position(|t| (t.orientation() + first.orientation()) % T::SIZE == 0);
+
+        if let Some(second) = opposite.and_then(|i| pieces.remove(i)) {
+            pairs.push([solving(first), solving(second)]);
+        } else if let Some(second) = pieces.pop_front() {
+            pairs.push([
+                solving(first),
+                second.reference().rotate(first.orientation()),
+            ]);
+            pieces.push_front(second.rotate(first.orientation()));
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alg, commutator::types::ThreeCycle, moves::MoveKind};
+
+    fn letter<T: Sticker>(sticker: T) -> char {
+        char::from(b'A' + sticker.index() as u8)
+    }
+
+    fn apply_steps(state: FaceletCube, solution: &Solution) -> FaceletCube {
+        solution.steps.iter().fold(state, |acc, step| match step {
+            Step::Corners(pair) => acc.cycle(pair.cycle).unwrap(),
+            Step::Edges(pair) => acc.cycle(pair.cycle).unwrap(),
+            Step::Parity { parity, .. } => acc.swap(*parity).unwrap(),
+            Step::Twist { twist, .. } => acc.twist(*twist).unwrap(),
+            Step::Flip { flip, .. } => acc.flip(*flip).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_scramble_tracing() {
+        let scrambles = [
+            alg!("D F2 U' B2 F2 U2 L2 D B2 D2 U' F2 U' F2 R' B R' D R2 D2 R' F' L R'"),
+            alg!("R2 D' B2 U L2 D R2 D2 B2 U' F2 R B D' U2 L B' L' F' U' R'"),
+            alg!("U R F D L B"),
+            alg!("[R' D' R D R' D' R D, U] [(M' U)4, U]"),
+        ];
+        let options = SolverOptions {
+            max_depth: 0,
+            parity_depth: 0,
+            ..Default::default()
+        };
+
+        for scramble in scrambles {
+            let state = FaceletCube::default().apply_alg(&scramble);
+            let solution = solve(&scramble, &options, letter, letter).unwrap();
+
+            assert_eq!(FaceletCube::default(), apply_steps(state, &solution));
+        }
+    }
+
+    #[test]
+    fn test_solution() {
+        let scramble = alg!("[U, R' D' R] [R U R', D]");
+        let options = SolverOptions {
+            allowed_moves: vec![MoveKind::U, MoveKind::R, MoveKind::D],
+            max_depth: 4,
+            ..Default::default()
+        };
+        let solution = solve(&scramble, &options, letter, letter).unwrap();
+        let alg = solution.alg().unwrap();
+        let state = FaceletCube::default().apply_alg(&scramble).apply_alg(&alg);
+
+        assert_eq!(2, solution.steps.len());
+        assert_eq!(FaceletCube::default(), state);
+    }
+}
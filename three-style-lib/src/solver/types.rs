@@ -0,0 +1,141 @@
+use crate::{
+    commutator::types::{Commutator, CommutatorSequence, Cycle, Flip, Twist},
+    moves::{Alg, MoveKind},
+    parity::types::Parity,
+    sticker::{Corner, Edge},
+};
+use std::fmt;
+
+/// Parameters of the solver, the same constraints are used for every commutator search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverOptions {
+    pub corner_buffer: Corner,
+    pub edge_buffer: Edge,
+    /// Edge swapped with the edge buffer by the parity algorithm.
+    pub parity_edge: Edge,
+    pub allowed_moves: Vec<MoveKind>,
+    pub max_depth: u8,
+    pub parity_moves: Vec<MoveKind>,
+    pub parity_depth: u8,
+    /// Number of threads used by the 3-cycle searches.
+    pub threads: usize,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            corner_buffer: Corner::UFR,
+            edge_buffer: Edge::UF,
+            parity_edge: Edge::UR,
+            allowed_moves: vec![
+                MoveKind::U,
+                MoveKind::R,
+                MoveKind::D,
+                MoveKind::F,
+                MoveKind::M,
+            ],
+            max_depth: 6,
+            parity_moves: vec![MoveKind::R, MoveKind::U, MoveKind::F],
+            parity_depth: 14,
+            threads: 1,
+        }
+    }
+}
+
+/// 3-cycle from the buffer to a pair of targets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetPair<T> {
+    pub cycle: Cycle<T>,
+    pub letters: [char; 2],
+    pub commutator: Option<Commutator>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Corners(TargetPair<Corner>),
+    Edges(TargetPair<Edge>),
+    Parity {
+        parity: Parity,
+        letter: char,
+        alg: Option<Alg>,
+    },
+    Twist {
+        twist: Twist,
+        alg: Option<CommutatorSequence>,
+    },
+    Flip {
+        flip: Flip,
+        alg: Option<CommutatorSequence>,
+    },
+}
+
+impl Step {
+    /// Returns the non-reduced algorithm solving the step if one has been found.
+    pub fn alg(&self) -> Option<Alg> {
+        match self {
+            Step::Corners(pair) => pair.commutator.as_ref().map(Commutator::expand),
+            Step::Edges(pair) => pair.commutator.as_ref().map(Commutator::expand),
+            Step::Parity { alg, .. } => alg.clone(),
+            Step::Twist { alg, .. } | Step::Flip { alg, .. } => {
+                alg.as_ref().map(CommutatorSequence::expand)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (case, alg) = match self {
+            Step::Corners(pair) => (
+                pair.letters.iter().collect(),
+                pair.commutator.as_ref().map(|c| c.to_string()),
+            ),
+            Step::Edges(pair) => (
+                pair.letters.iter().collect(),
+                pair.commutator.as_ref().map(|c| c.to_string()),
+            ),
+            Step::Parity { letter, alg, .. } => (
+                format!("{letter} (parity)"),
+                alg.as_ref().map(|a| a.to_string()),
+            ),
+            Step::Twist { twist, alg } => (
+                format!("{twist} (twist)"),
+                alg.as_ref().map(|a| a.to_string()),
+            ),
+            Step::Flip { flip, alg } => (
+                format!("{flip} (flip)"),
+                alg.as_ref().map(|a| a.to_string()),
+            ),
+        };
+
+        write!(f, "{case}: {}", alg.unwrap_or("-".to_owned()))
+    }
+}
+
+/// Ordered steps solving a scramble: corners, parity, twists, edges then flips.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub steps: Vec<Step>,
+}
+
+impl Solution {
+    /// Returns the full non-reduced solution if an algorithm has been found for every step.
+    pub fn alg(&self) -> Option<Alg> {
+        self.steps
+            .iter()
+            .try_fold(Alg::default(), |acc, s| s.alg().map(|alg| acc + alg))
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self
+            .steps
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(f, "{s}")
+    }
+}
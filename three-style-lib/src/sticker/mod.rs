@@ -1,12 +1,58 @@
 mod constants;
+mod scheme;
 
 use crate::{
     error::Error,
     facelet::{Facelet, FaceletTarget},
 };
-use constants::{CORNER_FACELET_MAP, EDGE_FACELET_MAP};
 use std::{fmt, str::FromStr};
 
+pub(crate) use constants::{CORNER_FACELET_MAP, EDGE_FACELET_MAP};
+pub use scheme::LetterScheme;
+
+/// Common operations on corner and edge stickers, based on the sticker maps order
+/// where the stickers of a piece are contiguous and start with the reference (U/D or F/B) sticker.
+pub trait Sticker: Clone + Copy + PartialEq + Send + Sync + FaceletTarget + fmt::Display {
+    /// Number of stickers of a piece.
+    const SIZE: usize;
+
+    /// All the stickers in the sticker maps order.
+    const ALL: [Self; 24];
+
+    fn index(&self) -> usize;
+
+    /// Returns the orientation of the sticker relative to the reference sticker of the piece.
+    fn orientation(&self) -> usize {
+        self.index() % Self::SIZE
+    }
+
+    /// Returns the sticker of the same piece `n` steps further in the sticker order.
+    fn rotate(&self, n: usize) -> Self {
+        let index = self.index();
+        let base = index - index % Self::SIZE;
+        Self::ALL[base + (index + n) % Self::SIZE]
+    }
+
+    /// Returns the reference sticker of the piece.
+    fn reference(&self) -> Self {
+        Self::ALL[self.index() - self.orientation()]
+    }
+
+    /// Returns all the stickers of the piece starting from the current one.
+    fn piece_stickers(&self) -> Vec<Self> {
+        (0..Self::SIZE).map(|n| self.rotate(n)).collect()
+    }
+
+    fn is_same_piece(&self, other: &Self) -> bool {
+        let facelets = other.to_facelets();
+        self.to_facelets().iter().all(|f| facelets.contains(f))
+    }
+
+    fn from_facelet(facelet: Facelet) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_facelet() == facelet)
+    }
+}
+
 /// Layer based representation of corner stickers.
 #[rustfmt::skip]
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -27,6 +73,26 @@ impl FaceletTarget for Corner {
     }
 }
 
+impl Sticker for Corner {
+    const SIZE: usize = 3;
+
+    #[rustfmt::skip]
+    const ALL: [Self; 24] = [
+        Self::UBL, Self::BLU, Self::LUB,
+        Self::URB, Self::RBU, Self::BUR,
+        Self::UFR, Self::FRU, Self::RUF,
+        Self::ULF, Self::LFU, Self::FUL,
+        Self::DFL, Self::FLD, Self::LDF,
+        Self::DRF, Self::RFD, Self::FDR,
+        Self::DBR, Self::BRD, Self::RDB,
+        Self::DLB, Self::LBD, Self::BDL,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 impl FromStr for Corner {
     type Err = Error;
 
@@ -91,6 +157,30 @@ impl FaceletTarget for Edge {
     }
 }
 
+impl Sticker for Edge {
+    const SIZE: usize = 2;
+
+    #[rustfmt::skip]
+    const ALL: [Self; 24] = [
+        Self::UB, Self::BU,
+        Self::UR, Self::RU,
+        Self::UF, Self::FU,
+        Self::UL, Self::LU,
+        Self::FL, Self::LF,
+        Self::FR, Self::RF,
+        Self::BR, Self::RB,
+        Self::BL, Self::LB,
+        Self::DF, Self::FD,
+        Self::DR, Self::RD,
+        Self::DB, Self::BD,
+        Self::DL, Self::LD,
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 impl FromStr for Edge {
     type Err = Error;
 
@@ -130,3 +220,35 @@ impl fmt::Display for Edge {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sticker_order() {
+        assert!(Corner::ALL.iter().enumerate().all(|(i, c)| c.index() == i));
+        assert!(Edge::ALL.iter().enumerate().all(|(i, e)| e.index() == i));
+    }
+
+    #[test]
+    fn test_sticker_rotation() {
+        assert_eq!(Corner::FRU, Corner::UFR.rotate(1));
+        assert_eq!(Corner::RUF, Corner::UFR.rotate(2));
+        assert_eq!(Corner::UFR, Corner::RUF.rotate(1));
+        assert_eq!(Corner::UFR, Corner::RUF.reference());
+        assert_eq!(2, Corner::RUF.orientation());
+        assert_eq!(Edge::FU, Edge::UF.rotate(1));
+        assert_eq!(Edge::UF, Edge::FU.reference());
+    }
+
+    #[test]
+    fn test_same_piece() {
+        assert!(Corner::UFR.is_same_piece(&Corner::RUF));
+        assert!(!Corner::UFR.is_same_piece(&Corner::URB));
+        assert!(Edge::UF.is_same_piece(&Edge::FU));
+        assert!(!Edge::UF.is_same_piece(&Edge::UB));
+        assert_eq!(Some(Edge::FU), Edge::from_facelet(Facelet::F1));
+        assert_eq!(None, Corner::from_facelet(Facelet::F1));
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Corner, Edge, Sticker};
+use crate::error::{self, Error};
+use std::{collections::HashSet, path::Path, str::FromStr};
+
+/// Corner stickers in the Speffz lettering order (`A` to `X`).
+#[rustfmt::skip]
+const SPEFFZ_CORNERS: [Corner; 24] = [
+    Corner::UBL, Corner::URB, Corner::UFR, Corner::ULF,
+    Corner::LUB, Corner::LFU, Corner::LDF, Corner::LBD,
+    Corner::FUL, Corner::FRU, Corner::FDR, Corner::FLD,
+    Corner::RUF, Corner::RBU, Corner::RDB, Corner::RFD,
+    Corner::BUR, Corner::BLU, Corner::BDL, Corner::BRD,
+    Corner::DFL, Corner::DRF, Corner::DBR, Corner::DLB,
+];
+
+/// Edge stickers in the Speffz lettering order (`A` to `X`).
+#[rustfmt::skip]
+const SPEFFZ_EDGES: [Edge; 24] = [
+    Edge::UB, Edge::UR, Edge::UF, Edge::UL,
+    Edge::LU, Edge::LF, Edge::LD, Edge::LB,
+    Edge::FU, Edge::FR, Edge::FD, Edge::FL,
+    Edge::RU, Edge::RB, Edge::RD, Edge::RF,
+    Edge::BU, Edge::BL, Edge::BD, Edge::BR,
+    Edge::DF, Edge::DR, Edge::DB, Edge::DL,
+];
+
+const SPEFFZ_LETTERS: [char; 24] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X',
+];
+
+/// Maps corner and edge stickers to the letters used for memorization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetterScheme {
+    corners: [char; 24],
+    edges: [char; 24],
+}
+
+impl LetterScheme {
+    /// Creates a scheme from letters given in the Speffz sticker order,
+    /// i.e. faces `ULFRBD` each read clockwise from the top left sticker.
+    pub fn new(corners: [char; 24], edges: [char; 24]) -> Self {
+        let mut scheme = Self {
+            corners: ['\0'; 24],
+            edges: ['\0'; 24],
+        };
+
+        for (sticker, letter) in SPEFFZ_CORNERS.iter().zip(corners) {
+            scheme.corners[sticker.index()] = letter;
+        }
+        for (sticker, letter) in SPEFFZ_EDGES.iter().zip(edges) {
+            scheme.edges[sticker.index()] = letter;
+        }
+
+        scheme
+    }
+
+    pub fn speffz() -> Self {
+        Self::new(SPEFFZ_LETTERS, SPEFFZ_LETTERS)
+    }
+
+    /// Loads a scheme from a file, see the [`FromStr`] implementation for the format.
+    pub fn from_file<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let content = error::read_to_string(path.as_ref())?;
+
+        Self::from_str(&content)
+    }
+
+    pub fn corner_letter(&self, corner: Corner) -> char {
+        self.corners[corner.index()]
+    }
+
+    pub fn edge_letter(&self, edge: Edge) -> char {
+        self.edges[edge.index()]
+    }
+
+    pub fn corner(&self, letter: char) -> Option<Corner> {
+        let index = self.corners.iter().position(|&l| l == letter)?;
+        Some(Corner::ALL[index])
+    }
+
+    pub fn edge(&self, letter: char) -> Option<Edge> {
+        let index = self.edges.iter().position(|&l| l == letter)?;
+        Some(Edge::ALL[index])
+    }
+}
+
+impl FromStr for LetterScheme {
+    type Err = Error;
+
+    /// Parses 24 corner letters followed by 24 edge letters in the Speffz sticker order,
+    /// whitespaces are ignored and letters must be unique for each piece type.
+    /// Example: `ABCD EFGH IJKL MNOP QRST UVWX ABCD EFGH IJKL MNOP QRST UVWX`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let letters = s.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>();
+        let error = || Error::InvalidLetterScheme(s.trim().to_owned());
+
+        if letters.len() != 48 {
+            return Err(error());
+        }
+
+        let (corners, edges) = letters.split_at(24);
+        let is_unique = |letters: &[char]| letters.iter().collect::<HashSet<_>>().len() == 24;
+
+        if !is_unique(corners) || !is_unique(edges) {
+            return Err(error());
+        }
+
+        let corners = corners.try_into().map_err(|_| error())?;
+        let edges = edges.try_into().map_err(|_| error())?;
+
+        Ok(Self::new(corners, edges))
+    }
+}
+
+impl Default for LetterScheme {
+    fn default() -> Self {
+        Self::speffz()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speffz_letters() {
+        let scheme = LetterScheme::speffz();
+
+        assert_eq!('C', scheme.corner_letter(Corner::UFR));
+        assert_eq!('J', scheme.corner_letter(Corner::FRU));
+        assert_eq!('M', scheme.corner_letter(Corner::RUF));
+        assert_eq!('X', scheme.corner_letter(Corner::DLB));
+        assert_eq!('C', scheme.edge_letter(Edge::UF));
+        assert_eq!('I', scheme.edge_letter(Edge::FU));
+        assert_eq!('T', scheme.edge_letter(Edge::BR));
+    }
+
+    #[test]
+    fn test_letter_lookup() {
+        let scheme = LetterScheme::speffz();
+
+        for corner in Corner::ALL {
+            assert_eq!(Some(corner), scheme.corner(scheme.corner_letter(corner)));
+        }
+        for edge in Edge::ALL {
+            assert_eq!(Some(edge), scheme.edge(scheme.edge_letter(edge)));
+        }
+
+        assert_eq!(None, scheme.corner('Z'));
+    }
+
+    #[test]
+    fn test_custom_scheme() {
+        let input = "abcdefghijklmnopqrstuvwx ABCDEFGHIJKLMNOPQRSTUVWX";
+        let scheme = LetterScheme::from_str(input).unwrap();
+
+        assert_eq!('c', scheme.corner_letter(Corner::UFR));
+        assert_eq!('C', scheme.edge_letter(Edge::UF));
+        assert_eq!(Some(Corner::FRU), scheme.corner('j'));
+        assert_eq!(None, scheme.edge('j'));
+
+        let error = |s: &str| Err(Error::InvalidLetterScheme(s.to_owned()));
+
+        assert_eq!(error("ABCD"), LetterScheme::from_str("ABCD"));
+        assert_eq!(
+            error(&input.replace('b', "a")),
+            LetterScheme::from_str(&input.replace('b', "a"))
+        );
+    }
+}